@@ -0,0 +1,305 @@
+// programs/prediction_market/src/collateral.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeAmount;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state;
+use anchor_spl::token_interface::{
+    close_account, harvest_withheld_tokens_to_mint, transfer_checked, CloseAccount,
+    HarvestWithheldTokensToMint, Mint, Token2022, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::*;
+use crate::errors::*;
+
+/// Token accounts of an SPL collateral market. Instructions take them as
+/// optional accounts; they are required (and checked) only when the market
+/// has `collateral`, SOL markets leave them out.
+pub struct TokenVault<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> TokenVault<'a, 'info> {
+    pub const SEED_PREFIX: &'static [u8] = b"vault";
+    
+    pub fn load(
+        market: &Market,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Result<Option<Self>> {
+        let Some(collateral) = &market.collateral else {
+            return Ok(None);
+        };
+        
+        let (Some(mint), Some(vault), Some(token_program)) =
+            (mint.as_ref(), vault.as_ref(), token_program.as_ref())
+        else {
+            return err!(PredictionMarketError::MissingCollateralAccounts);
+        };
+        
+        require_keys_eq!(
+            mint.key(),
+            collateral.mint,
+            PredictionMarketError::InvalidCollateralMint
+        );
+        require_keys_eq!(
+            *mint.to_account_info().owner,
+            token_program.key(),
+            PredictionMarketError::InvalidCollateralMint
+        );
+        require_keys_eq!(
+            vault.key(),
+            collateral.vault,
+            PredictionMarketError::InvalidTokenAccount
+        );
+        
+        Ok(Some(Self { mint, vault, token_program }))
+    }
+    
+    /// A user or treasury token account of the collateral mint.
+    pub fn token_account<'b>(
+        &self,
+        account: &'b Option<InterfaceAccount<'info, TokenAccount>>,
+        owner: Pubkey,
+    ) -> Result<&'b InterfaceAccount<'info, TokenAccount>> {
+        let account = account
+            .as_ref()
+            .ok_or(PredictionMarketError::MissingCollateralAccounts)?;
+        
+        require!(
+            account.mint == self.mint.key() && account.owner == owner,
+            PredictionMarketError::InvalidTokenAccount
+        );
+        
+        Ok(account)
+    }
+    
+    /// Vault balance read from the account data, so it is current after CPIs.
+    pub fn balance(&self) -> Result<u64> {
+        accessor::amount(&self.vault.to_account_info())
+    }
+    
+    /// Moves `amount` from a user's token account into the vault and returns
+    /// what the vault actually received (less with transfer-fee mints).
+    pub fn deposit(
+        &self,
+        from: &InterfaceAccount<'info, TokenAccount>,
+        authority: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<u64> {
+        let balance_before = self.balance()?;
+        
+        let cpi_context = CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.vault.to_account_info(),
+                authority: authority.clone(),
+            },
+        );
+        transfer_checked(cpi_context, amount, self.mint.decimals)?;
+        
+        let received = self.balance()?
+            .checked_sub(balance_before)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        
+        Ok(received)
+    }
+    
+    /// Pays `amount` out of the vault, signed by the market PDA.
+    pub fn pay_out(
+        &self,
+        market: &Account<'info, Market>,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        
+        let market_id = market.market_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"market",
+            market.creator.as_ref(),
+            market_id.as_ref(),
+            &[market.bump],
+        ]];
+        let cpi_context = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_context, amount, self.mint.decimals)
+    }
+    
+    /// Sends what is left in the vault to `treasury` and closes it, rent
+    /// going to `rent_recipient`. Transfer fees withheld in the vault are
+    /// harvested to the mint first, Token-2022 refuses to close it otherwise;
+    /// the mint must be writable for that. Returns the swept amount.
+    pub fn sweep_and_close(
+        &self,
+        market: &Account<'info, Market>,
+        treasury: &InterfaceAccount<'info, TokenAccount>,
+        rent_recipient: &AccountInfo<'info>,
+    ) -> Result<u64> {
+        let swept = self.balance()?;
+        self.pay_out(market, treasury, swept)?;
+        
+        if self.withheld_fees()? > 0 {
+            let cpi_context = CpiContext::new(
+                self.token_program.to_account_info(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+            );
+            harvest_withheld_tokens_to_mint(cpi_context, vec![self.vault.to_account_info()])?;
+        }
+        
+        let market_id = market.market_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"market",
+            market.creator.as_ref(),
+            market_id.as_ref(),
+            &[market.bump],
+        ]];
+        let cpi_context = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: rent_recipient.clone(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(cpi_context)?;
+        
+        Ok(swept)
+    }
+    
+    /// Transfer fees withheld in the vault from incoming transfers.
+    fn withheld_fees(&self) -> Result<u64> {
+        let vault_info = self.vault.to_account_info();
+        if *vault_info.owner != Token2022::id() {
+            return Ok(0);
+        }
+        
+        let data = vault_info.try_borrow_data()?;
+        let vault = StateWithExtensions::<state::Account>::unpack(&data)?;
+        Ok(vault
+            .get_extension::<TransferFeeAmount>()
+            .map(|fees| u64::from(fees.withheld_amount))
+            .unwrap_or(0))
+    }
+}
+
+/// Token-2022 mint extensions a collateral mint may carry. Anything that can
+/// freeze, claw back or block the vault's tokens, or needs extra accounts on
+/// every transfer (hooks, confidential transfers), is rejected.
+pub fn is_supported_extension(extension: ExtensionType) -> bool {
+    matches!(
+        extension,
+        ExtensionType::TransferFeeConfig
+            | ExtensionType::MintCloseAuthority
+            | ExtensionType::InterestBearingConfig
+            | ExtensionType::MetadataPointer
+            | ExtensionType::TokenMetadata
+            | ExtensionType::GroupPointer
+            | ExtensionType::TokenGroup
+            | ExtensionType::GroupMemberPointer
+            | ExtensionType::TokenGroupMember
+    )
+}
+
+/// Token-2022 mints that withhold a fee on every transfer.
+pub fn has_transfer_fee(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != Token2022::id() {
+        return Ok(false);
+    }
+    
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<state::Mint>::unpack(&data)?;
+    Ok(mint_state
+        .get_extension_types()?
+        .contains(&ExtensionType::TransferFeeConfig))
+}
+
+/// Rejects collateral mints with unsupported extensions; legacy SPL mints
+/// have none.
+pub fn check_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != Token2022::id() {
+        return Ok(());
+    }
+    
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<state::Mint>::unpack(&data)?;
+    for extension in mint_state.get_extension_types()? {
+        require!(
+            is_supported_extension(extension),
+            PredictionMarketError::UnsupportedMintExtension
+        );
+    }
+    
+    Ok(())
+}
+
+/// Scales an amount with `TOKEN_AMOUNT_DECIMALS` to a mint with `decimals`.
+/// Never rounds down to zero so a minimum stays a minimum.
+pub fn scale_to_decimals(amount: u64, from_decimals: u8, decimals: u8) -> Result<u64> {
+    let scaled = if decimals >= from_decimals {
+        10u64
+            .checked_pow((decimals - from_decimals) as u32)
+            .and_then(|factor| amount.checked_mul(factor))
+            .ok_or(PredictionMarketError::MathOverflow)?
+    } else {
+        10u64
+            .checked_pow((from_decimals - decimals) as u32)
+            .map(|factor| amount / factor)
+            .unwrap_or(0)
+    };
+    
+    Ok(scaled.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn scales_minimums_to_mint_decimals() {
+        // 0.01 token at 6 decimals
+        assert_eq!(scale_to_decimals(10_000, 6, 6).unwrap(), 10_000);
+        assert_eq!(scale_to_decimals(10_000, 6, 9).unwrap(), 10_000_000);
+        assert_eq!(scale_to_decimals(10_000, 6, 2).unwrap(), 1);
+        // Never below one base unit
+        assert_eq!(scale_to_decimals(10_000, 6, 0).unwrap(), 1);
+        assert!(scale_to_decimals(u64::MAX, 6, 9).is_err());
+    }
+    
+    #[test]
+    fn extensions_that_can_lock_the_vault_are_rejected() {
+        assert!(is_supported_extension(ExtensionType::TransferFeeConfig));
+        assert!(is_supported_extension(ExtensionType::TokenMetadata));
+        
+        for extension in [
+            ExtensionType::NonTransferable,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::TransferHook,
+            ExtensionType::DefaultAccountState,
+            ExtensionType::ConfidentialTransferMint,
+        ] {
+            assert!(!is_supported_extension(extension));
+        }
+    }
+}
@@ -0,0 +1,304 @@
+// programs/prediction_market/src/cpmm.rs
+
+// Constant-product market maker for two-option markets. The pool holds
+// `reserves` of both outcome shares, backed by complete sets like every
+// other share: one unit of collateral mints one share of each option.
+//
+// Buying option `i` with `a` collateral mints `a` sets into the pool and
+// takes out enough `i` shares to restore `reserves[0] * reserves[1]`;
+// selling does the reverse, burning sets for the collateral paid out. The
+// swap fee stays in the pool as extra sets, so the product only grows and
+// liquidity providers earn it on withdrawal.
+//
+// Invariant kept by every operation: `option_pools[i] + reserves[i]` is the
+// same for both options and equals the collateral backing them.
+
+use anchor_lang::prelude::*;
+use crate::errors::*;
+
+const BPS: u128 = 10_000;
+
+/// Collateral moved by a trade and the reserves after it.
+#[derive(Debug, PartialEq)]
+pub struct Swap {
+    pub collateral: u64,
+    pub reserves: [u64; 2],
+}
+
+/// LP tokens minted for a deposit, the reserves after it and the shares of
+/// each option returned to the provider to keep the pool's price.
+#[derive(Debug, PartialEq)]
+pub struct Deposit {
+    pub lp_tokens: u64,
+    pub reserves: [u64; 2],
+    pub returned: [u64; 2],
+}
+
+/// Collateral to buy `shares` of `option`, fee included, rounded up in the
+/// pool's favor.
+pub fn buy(reserves: [u64; 2], option: usize, shares: u64, fee_bps: u16) -> Result<Swap> {
+    let (bought, other) = (reserves[option] as u128, reserves[1 - option] as u128);
+    require!(bought > 0 && other > 0, PredictionMarketError::InsufficientLiquidity);
+    
+    // Sets `x` to mint so that (bought + x - shares) * (other + x) = k:
+    // x = (sqrt((bought + other - shares)^2 + 4 * shares * other) - (bought + other - shares)) / 2
+    let k = bought * other;
+    let shares = shares as u128;
+    let discriminant = (bought + other)
+        .abs_diff(shares)
+        .checked_pow(2)
+        .zip(shares.checked_mul(other).and_then(|product| product.checked_mul(4)))
+        .and_then(|(square, product)| square.checked_add(product))
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let root = isqrt(discriminant);
+    let mut invested = if bought + other >= shares {
+        (root - (bought + other - shares)) / 2
+    } else {
+        (root + (shares - bought - other)) / 2
+    };
+    while !holds_product(bought + invested, shares, other + invested, k)? {
+        invested += 1;
+    }
+    
+    // The fee comes on top of the sets that price the trade
+    let collateral = (invested * BPS).div_ceil(BPS - fee_bps as u128);
+    
+    Ok(Swap {
+        collateral: to_u64(collateral)?,
+        reserves: ordered(option, bought + collateral - shares, other + collateral)?,
+    })
+}
+
+/// Collateral paid for selling `shares` of `option`, fee deducted, rounded
+/// down in the pool's favor.
+pub fn sell(reserves: [u64; 2], option: usize, shares: u64, fee_bps: u16) -> Result<Swap> {
+    let (sold, other) = (reserves[option] as u128, reserves[1 - option] as u128);
+    require!(sold > 0 && other > 0, PredictionMarketError::InsufficientLiquidity);
+    
+    // Sets `x` to burn so that (sold + shares - x) * (other - x) = k:
+    // x = (sold + shares + other - sqrt((sold + shares - other)^2 + 4 * sold * other)) / 2
+    let k = sold * other;
+    let shares = shares as u128;
+    let discriminant = (sold + shares)
+        .abs_diff(other)
+        .checked_pow(2)
+        .zip(k.checked_mul(4))
+        .and_then(|(square, product)| square.checked_add(product))
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let mut burned = (sold + shares + other).saturating_sub(isqrt(discriminant)).div_ceil(2);
+    while burned > 0 && !holds_product(sold + shares, burned, other.saturating_sub(burned), k)? {
+        burned -= 1;
+    }
+    
+    // The fee stays in the pool as sets that are not burned
+    let fee = (burned * fee_bps as u128).div_ceil(BPS);
+    let collateral = burned - fee;
+    
+    Ok(Swap {
+        collateral: to_u64(collateral)?,
+        reserves: ordered(option, sold + shares - collateral, other - collateral)?,
+    })
+}
+
+/// Adds `amount` complete sets to the pool. The first deposit sets a 50/50
+/// price; later ones add to both reserves in proportion and return the
+/// surplus of the cheaper option's shares, so prices do not move.
+pub fn add_liquidity(reserves: [u64; 2], lp_supply: u64, amount: u64) -> Result<Deposit> {
+    require!(amount > 0, PredictionMarketError::InvalidLiquidity);
+    
+    if lp_supply == 0 {
+        return Ok(Deposit {
+            lp_tokens: amount,
+            reserves: [amount, amount],
+            returned: [0, 0],
+        });
+    }
+    
+    let largest = reserves[0].max(reserves[1]) as u128;
+    let mut deposit = Deposit {
+        lp_tokens: to_u64(amount as u128 * lp_supply as u128 / largest)?,
+        reserves,
+        returned: [0, 0],
+    };
+    require!(deposit.lp_tokens > 0, PredictionMarketError::InvalidLiquidity);
+    
+    for (option, reserve) in reserves.iter().enumerate() {
+        let added = to_u64(amount as u128 * *reserve as u128 / largest)?;
+        deposit.reserves[option] = reserve
+            .checked_add(added)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        deposit.returned[option] = amount - added;
+    }
+    
+    Ok(deposit)
+}
+
+/// Shares of each option backing `lp_tokens` out of `lp_supply`, rounded down.
+pub fn withdrawal(reserves: [u64; 2], lp_supply: u64, lp_tokens: u64) -> Result<[u64; 2]> {
+    require!(
+        lp_tokens > 0 && lp_tokens <= lp_supply,
+        PredictionMarketError::InsufficientPosition
+    );
+    
+    let share = |reserve: u64| reserve as u128 * lp_tokens as u128 / lp_supply as u128;
+    Ok([to_u64(share(reserves[0]))?, to_u64(share(reserves[1]))?])
+}
+
+/// Prices in basis points; an option is as cheap as its reserve is deep.
+pub fn prices_bps(reserves: [u64; 2]) -> Vec<u64> {
+    let total = reserves[0] as u128 + reserves[1] as u128;
+    if total == 0 {
+        return vec![5_000, 5_000];
+    }
+    
+    vec![
+        (reserves[1] as u128 * 10_000 / total) as u64,
+        (reserves[0] as u128 * 10_000 / total) as u64,
+    ]
+}
+
+/// `(first - removed) * second >= k`, false when `removed` exceeds `first`.
+fn holds_product(first: u128, removed: u128, second: u128, k: u128) -> Result<bool> {
+    let Some(first) = first.checked_sub(removed) else {
+        return Ok(false);
+    };
+    
+    Ok(first
+        .checked_mul(second)
+        .map(|product| product >= k)
+        .unwrap_or(true))
+}
+
+fn ordered(option: usize, traded: u128, other: u128) -> Result<[u64; 2]> {
+    let mut reserves = [to_u64(other)?; 2];
+    reserves[option] = to_u64(traded)?;
+    Ok(reserves)
+}
+
+fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| error!(PredictionMarketError::MathOverflow))
+}
+
+/// Integer square root, rounded down.
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    
+    // Newton's method from a power of two above the root
+    let mut x = 1u128 << (value.ilog2() / 2 + 1);
+    loop {
+        let next = (x + value / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn integer_square_root() {
+        for value in [0u128, 1, 2, 3, 4, 15, 16, 17, 1 << 64, u64::MAX as u128, u128::MAX] {
+            let root = isqrt(value);
+            assert!(root * root <= value);
+            assert!((root + 1).checked_mul(root + 1).map(|square| square > value).unwrap_or(true));
+        }
+    }
+    
+    #[test]
+    fn first_deposit_sets_even_odds() {
+        let deposit = add_liquidity([0, 0], 0, 1_000).unwrap();
+        assert_eq!(deposit, Deposit { lp_tokens: 1_000, reserves: [1_000, 1_000], returned: [0, 0] });
+        assert_eq!(prices_bps(deposit.reserves), vec![5_000, 5_000]);
+    }
+    
+    #[test]
+    fn buying_keeps_the_product_and_moves_the_price() {
+        let reserves = [1_000_000, 1_000_000];
+        let swap = buy(reserves, 0, 400_000, 0).unwrap();
+        
+        // (1_219_804 - 400_000) * 1_219_804 >= 1e12, one set less falls short
+        assert_eq!(swap.collateral, 219_804);
+        assert_eq!(swap.reserves, [819_804, 1_219_804]);
+        assert!(!holds_product(1_219_803, 400_000, 1_219_803, 1_000_000_000_000).unwrap());
+        assert!(swap.reserves[0] as u128 * swap.reserves[1] as u128 >= 1_000_000_000_000);
+        
+        let prices = prices_bps(swap.reserves);
+        assert!(prices[0] > 5_000 && prices[1] < 5_000);
+    }
+    
+    #[test]
+    fn fees_stay_in_the_pool() {
+        let reserves = [1_000_000, 3_000_000];
+        let without_fee = buy(reserves, 1, 500_000, 0).unwrap();
+        let with_fee = buy(reserves, 1, 500_000, 200).unwrap();
+        
+        assert!(with_fee.collateral > without_fee.collateral);
+        let product = |r: [u64; 2]| r[0] as u128 * r[1] as u128;
+        assert!(product(with_fee.reserves) > product(without_fee.reserves));
+        
+        let sold = sell(with_fee.reserves, 1, 500_000, 200).unwrap();
+        assert!(sold.collateral < with_fee.collateral);
+        assert!(product(sold.reserves) > product(reserves));
+    }
+    
+    #[test]
+    fn round_trip_never_profits_the_trader() {
+        let mut reserves = [7_000_000, 2_000_000];
+        for shares in [1, 999, 250_000, 30_000_000] {
+            for option in 0..2 {
+                let bought = buy(reserves, option, shares, 0).unwrap();
+                let sold = sell(bought.reserves, option, shares, 0).unwrap();
+                assert!(sold.collateral <= bought.collateral);
+                // Rounding costs a few units at most, a little more at extreme prices
+                assert!(bought.collateral - sold.collateral <= 2 + bought.collateral / 1_000_000);
+                reserves = sold.reserves;
+            }
+        }
+    }
+    
+    #[test]
+    fn trades_back_every_share() {
+        // Pool shares plus trader shares stay equal per option
+        let mut reserves = [5_000_000, 5_000_000];
+        let mut held = [0u64; 2];
+        let mut collateral = 5_000_000u64;
+        for (option, shares) in [(0, 3_000_000), (1, 12_345), (0, 777), (1, 9_000_000)] {
+            let swap = buy(reserves, option, shares, 150).unwrap();
+            collateral += swap.collateral;
+            held[option] += shares;
+            reserves = swap.reserves;
+        }
+        let swap = sell(reserves, 0, 1_000_000, 150).unwrap();
+        collateral -= swap.collateral;
+        held[0] -= 1_000_000;
+        reserves = swap.reserves;
+        
+        assert_eq!(held[0] + reserves[0], collateral);
+        assert_eq!(held[1] + reserves[1], collateral);
+    }
+    
+    #[test]
+    fn later_deposits_keep_the_price() {
+        let reserves = [500_000, 2_000_000];
+        let deposit = add_liquidity(reserves, 1_000_000, 400_000).unwrap();
+        
+        assert_eq!(deposit.lp_tokens, 200_000);
+        assert_eq!(deposit.reserves, [600_000, 2_400_000]);
+        assert_eq!(deposit.returned, [300_000, 0]);
+        assert_eq!(prices_bps(deposit.reserves), prices_bps(reserves));
+        
+        assert_eq!(withdrawal(deposit.reserves, 1_200_000, 200_000).unwrap(), [100_000, 400_000]);
+        assert!(withdrawal(deposit.reserves, 1_200_000, 1_200_001).is_err());
+    }
+    
+    #[test]
+    fn empty_pool_cannot_trade() {
+        assert!(buy([0, 0], 0, 1, 0).is_err());
+        assert!(sell([0, 10], 1, 1, 0).is_err());
+    }
+}
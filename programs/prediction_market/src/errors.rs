@@ -68,4 +68,136 @@ pub enum PredictionMarketError {
     
     #[msg("No bets placed yet")]
     NoBetsPlaced,
-}
\ No newline at end of file
+    
+    #[msg("No fees available to withdraw")]
+    NothingToWithdraw,
+    
+    #[msg("Invalid platform configuration")]
+    InvalidConfig,
+    
+    #[msg("Invalid admin account")]
+    InvalidAdmin,
+    
+    #[msg("Fee recipient is not the platform treasury")]
+    InvalidTreasury,
+    
+    #[msg("Market is not cancelled")]
+    MarketNotCancelled,
+    
+    #[msg("Bets have already been placed")]
+    BetsAlreadyPlaced,
+    
+    #[msg("Market cannot be closed yet")]
+    MarketNotClosable,
+    
+    #[msg("Market does not hold enough funds for this payout")]
+    InsufficientMarketFunds,
+    
+    #[msg("Withdrawal exceeds position")]
+    InsufficientPosition,
+    
+    #[msg("Market uses a different resolution mode")]
+    WrongResolutionMode,
+    
+    #[msg("Invalid oracle account")]
+    InvalidOracleAccount,
+    
+    #[msg("Oracle price is not available")]
+    OraclePriceUnavailable,
+    
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+    
+    #[msg("Oracle price exponent does not match the market")]
+    OracleExponentMismatch,
+    
+    #[msg("Winning option must be given in manual mode only")]
+    InvalidWinningOption,
+    
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    
+    #[msg("Proposal has already been disputed")]
+    AlreadyDisputed,
+    
+    #[msg("Proposal has not been disputed")]
+    NotDisputed,
+    
+    #[msg("Bond recipient is not the winning party")]
+    InvalidBondRecipient,
+    
+    #[msg("Resolution is in progress")]
+    ResolutionInProgress,
+    
+    #[msg("Invalid committee or threshold")]
+    InvalidCommittee,
+    
+    #[msg("Signer is not a committee member")]
+    NotCommitteeMember,
+    
+    #[msg("Committee member has already voted")]
+    AlreadyVoted,
+    
+    #[msg("Commit phase is not open")]
+    CommitPhaseClosed,
+    
+    #[msg("Reveal phase is not open")]
+    RevealPhaseClosed,
+    
+    #[msg("Voting is still in progress")]
+    VotingInProgress,
+    
+    #[msg("No vote commitment to reveal")]
+    NoCommitment,
+    
+    #[msg("Revealed vote does not match the commitment")]
+    InvalidReveal,
+    
+    #[msg("Scoring weights must sum to 100 and stay within platform bounds")]
+    InvalidScoringWeights,
+    
+    #[msg("Token collateral accounts are required for this market")]
+    MissingCollateralAccounts,
+    
+    #[msg("Invalid collateral mint")]
+    InvalidCollateralMint,
+    
+    #[msg("Token account does not match the market collateral or owner")]
+    InvalidTokenAccount,
+    
+    #[msg("Collateral mint has an unsupported Token-2022 extension")]
+    UnsupportedMintExtension,
+    
+    #[msg("Share mint and holder accounts must cover every option, in order")]
+    InvalidShareAccounts,
+    
+    #[msg("Share amount must be greater than zero")]
+    InvalidShareAmount,
+    
+    #[msg("Instruction not available for this market's pricing mode")]
+    WrongPricingMode,
+    
+    #[msg("LMSR liquidity must be greater than zero")]
+    InvalidLiquidity,
+    
+    #[msg("Trade price moved past the given limit")]
+    SlippageExceeded,
+    
+    #[msg("The pool has no liquidity")]
+    InsufficientLiquidity,
+    
+    #[msg("Swap fee exceeds the maximum")]
+    InvalidSwapFee,
+    
+    #[msg("Oracle confidence interval straddles the threshold")]
+    OraclePriceUncertain,
+    
+    #[msg("Dispute settlement deadline has passed")]
+    SettlementDeadlinePassed,
+    
+    #[msg("Dispute settlement deadline has not passed yet")]
+    SettlementPending,
+}
@@ -1,14 +1,22 @@
 // programs/prediction_market/events.rs
 
 use anchor_lang::prelude::*;
+use crate::state::{PricingMode, ResolutionMode, ScoringWeights, TiePolicy, VoidReason};
 
 #[event]
 pub struct MarketCreated {
     pub market: Pubkey,
     pub creator: Pubkey,
+    pub resolver: Pubkey,
     pub market_id: u64,
     pub end_time: i64,
     pub options_count: u8,
+    pub resolution_mode: ResolutionMode,
+    pub tie_policy: TiePolicy,
+    pub scoring_weights: ScoringWeights,
+    pub collateral_mint: Option<Pubkey>, // None: SOL
+    pub pricing: PricingMode,
+    pub subsidy: u64,
 }
 
 #[event]
@@ -22,15 +30,118 @@ pub struct BetPlaced {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct BetWithdrawn {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub option_index: u8,
+    pub amount: u64,
+    pub penalty: u64,
+    pub payout: u64,
+    pub new_pool_size: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MarketResolved {
     pub market: Pubkey,
     pub winning_option: u8,
+    pub winning_options: Vec<u8>, // Beraberlikte pay bölüşen seçenekler
     pub total_pool: u64,
     pub winning_pool: u64,
     pub resolution_time: i64,
 }
 
+#[event]
+pub struct OracleResolved {
+    pub market: Pubkey,
+    pub price_feed: Pubkey,
+    pub price: i64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+#[event]
+pub struct MarketVoided {
+    pub market: Pubkey,
+    pub reason: VoidReason,
+    pub total_pool: u64,
+    pub resolution_time: i64,
+}
+
+#[event]
+pub struct OutcomeProposed {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub option_index: u8,
+    pub bond: u64,
+    pub dispute_deadline: i64,
+}
+
+#[event]
+pub struct OutcomeDisputed {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    pub proposed_option: u8,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeSettled {
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub winning_option: u8,
+    pub bond_recipient: Pubkey,
+    pub bonds_awarded: u64,
+}
+
+#[event]
+pub struct DisputeExpired {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub disputer: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCommitted {
+    pub market: Pubkey,
+    pub member: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteRevealed {
+    pub market: Pubkey,
+    pub member: Pubkey,
+    pub option_index: u8,
+    pub tally: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitteeQuorumChanged {
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub previous_threshold: u8,
+    pub new_threshold: u8,
+}
+
+#[event]
+pub struct CommitteeResolved {
+    pub market: Pubkey,
+    pub winning_option: Option<u8>, // Yeter sayı yoksa None
+    pub tallies: Vec<u8>,
+    pub votes_cast: u8,
+    pub threshold: u8,
+    pub unrevealed: u8,
+    pub slashed: u64,
+}
+
 #[event]
 pub struct WinningsClaimed {
     pub market: Pubkey,
@@ -38,6 +149,30 @@ pub struct WinningsClaimed {
     pub payout: u64,
 }
 
+#[event]
+pub struct MarketCancelled {
+    pub market: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BetRefunded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MarketClosed {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub outstanding_claims: u64,
+    pub swept_to_treasury: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MarketPausedChanged {
     pub market: Pubkey,
@@ -45,6 +180,42 @@ pub struct MarketPausedChanged {
     pub admin: Pubkey,
 }
 
+#[event]
+pub struct FeesWithdrawn {
+    pub market: Pubkey,
+    pub treasury: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub config: Pubkey,
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminRenounced {
+    pub config: Pubkey,
+    pub previous_admin: Pubkey,
+}
+
 #[event]
 pub struct LeaderChanged {
     pub market: Pubkey,
@@ -58,4 +229,62 @@ pub struct VelocityLimitTriggered {
     pub user: Pubkey,
     pub attempted_amount: u64,
     pub limit: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct CompleteSetMinted {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub share_collateral: u64,
+}
+
+#[event]
+pub struct CompleteSetRedeemed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub share_collateral: u64,
+}
+
+#[event]
+pub struct SharesRedeemed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub option_index: u8,
+    pub amount: u64,
+    pub payout: u64,
+}
+
+#[event]
+pub struct SharesTraded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub option_index: u8,
+    pub shares: u64,
+    pub collateral: u64, // Alışta ödenen, satışta alınan
+    pub bought: bool,
+    pub prices_bps: Vec<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub lp_tokens: u64,
+    pub returned: [u64; 2], // Fiyatı korumak için geri verilen paylar
+    pub reserves: [u64; 2],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub lp_tokens: u64,
+    pub shares: [u64; 2],
+    pub payout: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,215 @@
+// programs/prediction_market/src/instructions/add_liquidity.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{self, mint_to, MintTo, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::collateral::*;
+use crate::cpmm;
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    // Holds the shares returned to keep the pool's price
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserBet::INIT_SPACE,
+        seeds = [
+            UserBet::SEED_PREFIX,
+            user.key().as_ref(),
+            market.key().as_ref()
+        ],
+        bump
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(
+        mut,
+        seeds = [
+            AmmPool::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump
+    )]
+    pub lp_mint: Account<'info, token::Mint>,
+    
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = user
+    )]
+    pub user_lp_account: Account<'info, token::TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub share_token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// CPMM markets: deposits `amount` complete sets into the pool for LP tokens,
+// at least `min_lp_tokens`. Liquidity stays in the pool until the market settles
+pub fn handler(ctx: Context<AddLiquidity>, amount: u64, min_lp_tokens: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_bet = &mut ctx.accounts.user_bet;
+    let clock = &ctx.accounts.clock;
+    
+    // Validations
+    require!(
+        market.amm.is_some(),
+        PredictionMarketError::WrongPricingMode
+    );
+    
+    require!(
+        market.is_active(),
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketEnded
+    );
+    
+    require!(
+        amount > 0,
+        PredictionMarketError::InvalidLiquidity
+    );
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    let amount = match &token_vault {
+        Some(token_vault) => {
+            let user_token_account = token_vault.token_account(
+                &ctx.accounts.user_token_account,
+                ctx.accounts.user.key(),
+            )?;
+            token_vault.deposit(user_token_account, &ctx.accounts.user.to_account_info(), amount)?
+        }
+        None => {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: market.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            amount
+        }
+    };
+    
+    let pool = market.cpmm_pool()?;
+    let deposit = cpmm::add_liquidity(pool.reserves, pool.lp_supply, amount)?;
+    require!(
+        deposit.lp_tokens >= min_lp_tokens,
+        PredictionMarketError::SlippageExceeded
+    );
+    
+    pool.reserves = deposit.reserves;
+    pool.lp_supply = pool.lp_supply
+        .checked_add(deposit.lp_tokens)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    let reserves = pool.reserves;
+    
+    // Returned shares become a position like bought ones
+    if user_bet.positions.is_empty() {
+        user_bet.user = ctx.accounts.user.key();
+        user_bet.market = market.key();
+        user_bet.positions = vec![0u64; market.options.len()];
+        user_bet.amount = 0;
+        user_bet.net_amount = 0;
+        user_bet.placed_at = clock.unix_timestamp;
+        user_bet.claimed = false;
+        user_bet.bump = ctx.bumps.user_bet;
+    }
+    
+    for (option_index, returned) in deposit.returned.iter().enumerate() {
+        if *returned == 0 {
+            continue;
+        }
+        
+        if user_bet.positions[option_index] == 0 {
+            market.option_bettors[option_index] = market.option_bettors[option_index]
+                .checked_add(1)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+        user_bet.positions[option_index] = user_bet.positions[option_index]
+            .checked_add(*returned)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        user_bet.amount = user_bet.amount
+            .checked_add(*returned)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        
+        market.option_pools[option_index] = market.option_pools[option_index]
+            .checked_add(*returned)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_pool = market.total_pool
+            .checked_add(*returned)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.record_pool_update(option_index as u8, clock.unix_timestamp);
+    }
+    user_bet.net_amount = user_bet.amount;
+    market.update_leader(clock);
+    
+    let market_id = market.market_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"market",
+        market.creator.as_ref(),
+        market_id.as_ref(),
+        &[market.bump],
+    ]];
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.share_token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            to: ctx.accounts.user_lp_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    mint_to(cpi_context, deposit.lp_tokens)?;
+    
+    emit!(LiquidityAdded {
+        market: market.key(),
+        provider: ctx.accounts.user.key(),
+        amount,
+        lp_tokens: deposit.lp_tokens,
+        returned: deposit.returned,
+        reserves,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
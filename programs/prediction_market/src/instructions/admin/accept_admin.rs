@@ -0,0 +1,35 @@
+// programs/prediction_market/src/instructions/admin/accept_admin.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(pending_admin.key())
+            @ PredictionMarketError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    pub pending_admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_admin = config.admin;
+    
+    config.admin = ctx.accounts.pending_admin.key();
+    config.pending_admin = None;
+    
+    emit!(AdminTransferred {
+        config: config.key(),
+        previous_admin,
+        new_admin: config.admin,
+    });
+    
+    Ok(())
+}
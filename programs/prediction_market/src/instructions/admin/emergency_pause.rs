@@ -17,7 +17,13 @@ pub struct EmergencyPause<'info> {
     )]
     pub market: Account<'info, Market>,
     
-    #[account(address = crate::PLATFORM_ADMIN @ PredictionMarketError::Unauthorized)]
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PredictionMarketError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
     pub admin: Signer<'info>,
 }
 
@@ -0,0 +1,54 @@
+// programs/prediction_market/src/instructions/admin/initialize_config.rs
+use anchor_lang::prelude::*;
+use crate::program::PredictionMarket;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    // Only the program's upgrade authority can create the config
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, PredictionMarket>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ PredictionMarketError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeConfig>,
+    admin: Pubkey,
+    params: ConfigParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    config.admin = admin;
+    config.pending_admin = None;
+    config.bump = ctx.bumps.config;
+    config.apply(params)?;
+    
+    emit!(ConfigUpdated {
+        config: config.key(),
+        admin: config.admin,
+        treasury: config.treasury,
+    });
+    
+    Ok(())
+}
@@ -1,6 +1,22 @@
 // programs/prediction_market/instructions/admin/mod.rs
 
 pub mod emergency_pause;
+pub mod withdraw_fees;
+pub mod initialize_config;
+pub mod update_config;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod renounce_admin;
+pub mod settle_dispute;
+pub mod update_committee_quorum;
 
 pub use emergency_pause::EmergencyPause;
-//pub(crate) use emergency_pause::__client_accounts_emergency_pause;
\ No newline at end of file
+pub use withdraw_fees::WithdrawFees;
+pub use initialize_config::InitializeConfig;
+pub use update_config::UpdateConfig;
+pub use propose_admin::ProposeAdmin;
+pub use accept_admin::AcceptAdmin;
+pub use renounce_admin::RenounceAdmin;
+pub use settle_dispute::SettleDispute;
+pub use update_committee_quorum::UpdateCommitteeQuorum;
+//pub(crate) use emergency_pause::__client_accounts_emergency_pause;
@@ -0,0 +1,38 @@
+// programs/prediction_market/src/instructions/admin/propose_admin.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PredictionMarketError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    require!(
+        new_admin != Pubkey::default() && new_admin != config.admin,
+        PredictionMarketError::InvalidAdmin
+    );
+    
+    // The new admin only takes over after calling `accept_admin`
+    config.pending_admin = Some(new_admin);
+    
+    emit!(AdminProposed {
+        config: config.key(),
+        admin: config.admin,
+        pending_admin: new_admin,
+    });
+    
+    Ok(())
+}
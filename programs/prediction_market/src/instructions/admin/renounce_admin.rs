@@ -0,0 +1,34 @@
+// programs/prediction_market/src/instructions/admin/renounce_admin.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RenounceAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PredictionMarketError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RenounceAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    // Irreversible: no key can sign for the default pubkey, so every
+    // admin-gated instruction is disabled from here on
+    config.admin = Pubkey::default();
+    config.pending_admin = None;
+    
+    emit!(AdminRenounced {
+        config: config.key(),
+        previous_admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,114 @@
+// programs/prediction_market/src/instructions/admin/settle_dispute.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::instructions::resolve_market::resolve_or_void;
+
+#[derive(Accounts)]
+pub struct SettleDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump,
+        has_one = creator @ PredictionMarketError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            ResolutionProposal::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = proposal.bump,
+        has_one = market
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    /// CHECK: checked against the proposal once the outcome is known
+    #[account(mut)]
+    pub bond_recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PredictionMarketError::Unauthorized,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: only receives the market rent if the market is voided
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    pub admin: Signer<'info>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<SettleDispute>, winning_option: u8) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    
+    require!(
+        ctx.accounts.market.phase == MarketPhase::Resolving,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    require!(
+        (winning_option as usize) < ctx.accounts.market.options.len(),
+        PredictionMarketError::InvalidOptionIndex
+    );
+    
+    // Once the deadline passes the dispute can only be expired
+    require!(
+        ctx.accounts.proposal.disputer.is_none() || ctx.accounts.proposal.can_settle(clock.unix_timestamp),
+        PredictionMarketError::SettlementDeadlinePassed
+    );
+    
+    // Whoever was right takes both bonds (and the proposal rent)
+    let recipient = ctx.accounts.proposal
+        .bond_recipient(winning_option)
+        .ok_or(PredictionMarketError::NotDisputed)?;
+    require_keys_eq!(
+        ctx.accounts.bond_recipient.key(),
+        recipient,
+        PredictionMarketError::InvalidBondRecipient
+    );
+    
+    let bonds_awarded = ctx.accounts.proposal.get_lamports();
+    ctx.accounts.proposal.close(ctx.accounts.bond_recipient.to_account_info())?;
+    
+    emit!(DisputeSettled {
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        winning_option,
+        bond_recipient: recipient,
+        bonds_awarded,
+    });
+    
+    resolve_or_void(
+        &mut ctx.accounts.market,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &[winning_option],
+        clock.unix_timestamp,
+    )
+}
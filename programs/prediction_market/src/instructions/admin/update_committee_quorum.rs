@@ -0,0 +1,65 @@
+// programs/prediction_market/src/instructions/admin/update_committee_quorum.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateCommitteeQuorum<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PredictionMarketError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateCommitteeQuorum>, threshold: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    require!(
+        market.resolution_mode == ResolutionMode::Committee,
+        PredictionMarketError::WrongResolutionMode
+    );
+    
+    // Fixed once the first vote is committed (which moves the market to
+    // Resolving), so the quorum cannot be tuned to the revealed votes
+    require!(
+        market.phase != MarketPhase::Resolving,
+        PredictionMarketError::VotingInProgress
+    );
+    require!(
+        market.phase == MarketPhase::Betting,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    require!(
+        threshold > 0 && threshold as usize <= market.committee.len(),
+        PredictionMarketError::InvalidCommittee
+    );
+    
+    let previous_threshold = market.committee_threshold;
+    market.committee_threshold = threshold;
+    
+    emit!(CommitteeQuorumChanged {
+        market: market.key(),
+        admin: ctx.accounts.admin.key(),
+        previous_threshold,
+        new_threshold: threshold,
+    });
+    
+    Ok(())
+}
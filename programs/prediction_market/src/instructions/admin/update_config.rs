@@ -0,0 +1,32 @@
+// programs/prediction_market/src/instructions/admin/update_config.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PredictionMarketError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    config.apply(params)?;
+    
+    emit!(ConfigUpdated {
+        config: config.key(),
+        admin: config.admin,
+        treasury: config.treasury,
+    });
+    
+    Ok(())
+}
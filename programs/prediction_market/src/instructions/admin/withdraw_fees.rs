@@ -0,0 +1,117 @@
+// programs/prediction_market/src/instructions/admin/withdraw_fees.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump,
+        has_one = market
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ PredictionMarketError::Unauthorized,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    pub admin: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn handler(ctx: Context<WithdrawFees>) -> Result<()> {
+    // Commissions stay refundable until the market is settled
+    require!(
+        ctx.accounts.market.phase == MarketPhase::Resolved,
+        PredictionMarketError::MarketNotResolved
+    );
+    
+    let market = &ctx.accounts.market;
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    let vault_info = fee_vault.to_account_info();
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    let amount = if let Some(token_vault) = &token_vault {
+        // Token fees stay in the market vault, `total_fees` tracks them
+        let amount = market.total_fees.saturating_sub(fee_vault.total_withdrawn);
+        require!(
+            amount > 0,
+            PredictionMarketError::NothingToWithdraw
+        );
+        
+        let treasury_token_account = token_vault.token_account(
+            &ctx.accounts.treasury_token_account,
+            ctx.accounts.treasury.key(),
+        )?;
+        token_vault.pay_out(market, treasury_token_account, amount)?;
+        amount
+    } else {
+        // Everything above the rent-exempt minimum is collected commission
+        let rent = Rent::get()?;
+        let amount = available_lamports(&vault_info, &rent);
+        
+        require!(
+            amount > 0,
+            PredictionMarketError::NothingToWithdraw
+        );
+        
+        transfer_lamports(&vault_info, &ctx.accounts.treasury.to_account_info(), amount, &rent)?;
+        amount
+    };
+    
+    fee_vault.total_withdrawn = fee_vault.total_withdrawn
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    emit!(FeesWithdrawn {
+        market: market.key(),
+        treasury: ctx.accounts.treasury.key(),
+        admin: ctx.accounts.admin.key(),
+        amount,
+        total_withdrawn: fee_vault.total_withdrawn,
+    });
+    
+    Ok(())
+}
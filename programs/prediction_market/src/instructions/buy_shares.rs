@@ -0,0 +1,173 @@
+// programs/prediction_market/src/instructions/buy_shares.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::collateral::*;
+
+#[derive(Accounts)]
+pub struct BuyShares<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserBet::INIT_SPACE,
+        seeds = [
+            UserBet::SEED_PREFIX,
+            user.key().as_ref(),
+            market.key().as_ref()
+        ],
+        bump
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// LMSR and CPMM markets: buys `shares` of an option at the market maker's price, up to `max_cost`
+pub fn handler(
+    ctx: Context<BuyShares>,
+    option_index: u8,
+    shares: u64,
+    max_cost: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_bet = &mut ctx.accounts.user_bet;
+    let clock = &ctx.accounts.clock;
+    
+    // Validations
+    require!(
+        market.pricing != PricingMode::Parimutuel,
+        PredictionMarketError::WrongPricingMode
+    );
+    
+    require!(
+        market.is_active(),
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketEnded
+    );
+    
+    require!(
+        (option_index as usize) < market.options.len(),
+        PredictionMarketError::InvalidOptionIndex
+    );
+    
+    require!(
+        shares > 0,
+        PredictionMarketError::InvalidShareAmount
+    );
+    
+    let cost = market.buy_cost(option_index, shares)?;
+    require!(
+        cost <= max_cost,
+        PredictionMarketError::SlippageExceeded
+    );
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.deposit(user_token_account, &ctx.accounts.user.to_account_info(), cost)?;
+    } else {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: market.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+    }
+    
+    // Update market state; pools count outstanding shares
+    if user_bet.position(option_index) == 0 {
+        market.option_bettors[option_index as usize] = market.option_bettors[option_index as usize]
+            .checked_add(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    
+    market.option_pools[option_index as usize] = market.option_pools[option_index as usize]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    market.total_pool = market.total_pool
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    market.record_pool_update(option_index, clock.unix_timestamp);
+    market.update_leader(clock);
+    
+    // Positions hold shares; there is no commission on top
+    if user_bet.positions.is_empty() {
+        user_bet.user = ctx.accounts.user.key();
+        user_bet.market = market.key();
+        user_bet.positions = vec![0u64; market.options.len()];
+        user_bet.amount = 0;
+        user_bet.net_amount = 0;
+        user_bet.placed_at = clock.unix_timestamp;
+        user_bet.claimed = false;
+        user_bet.bump = ctx.bumps.user_bet;
+    }
+    
+    user_bet.positions[option_index as usize] = user_bet.positions[option_index as usize]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    user_bet.amount = user_bet.amount
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    user_bet.net_amount = user_bet.amount;
+    
+    emit!(SharesTraded {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        option_index,
+        shares,
+        collateral: cost,
+        bought: true,
+        prices_bps: market.prices_bps(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
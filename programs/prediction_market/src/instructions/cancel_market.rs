@@ -0,0 +1,73 @@
+// programs/prediction_market/src/instructions/cancel_market.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        market.phase == MarketPhase::Betting || market.phase == MarketPhase::Resolving,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    // Bonds held for a pending proposal or committee ballot are released by
+    // `settle_dispute` / `expire_dispute` / `finalize_committee_vote` instead
+    require!(
+        !(market.phase == MarketPhase::Resolving
+            && matches!(
+                market.resolution_mode,
+                ResolutionMode::Optimistic | ResolutionMode::Committee
+            )),
+        PredictionMarketError::ResolutionInProgress
+    );
+    
+    // Admin can cancel at any time, creator only before the first bet
+    if authority != ctx.accounts.config.admin {
+        require!(
+            authority == market.creator,
+            PredictionMarketError::Unauthorized
+        );
+        require!(
+            market.total_pool == 0,
+            PredictionMarketError::BetsAlreadyPlaced
+        );
+    }
+    
+    market.phase = MarketPhase::Cancelled;
+    market.resolution_time = Some(clock.unix_timestamp);
+    
+    emit!(MarketCancelled {
+        market: market.key(),
+        cancelled_by: authority,
+        total_pool: market.total_pool,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
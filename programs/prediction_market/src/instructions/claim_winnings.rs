@@ -1,8 +1,11 @@
 // programs/prediction_market/src/instructions/claim_winnings.rs
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
 
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
@@ -33,11 +36,22 @@ pub struct ClaimWinnings<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<ClaimWinnings>) -> Result<()> {
-    let market = &ctx.accounts.market;
+    let market = &mut ctx.accounts.market;
     let user_bet = &ctx.accounts.user_bet;
     
     // Validations
@@ -51,35 +65,44 @@ pub fn handler(ctx: Context<ClaimWinnings>) -> Result<()> {
         PredictionMarketError::AlreadyClaimed
     );
     
-    let winner = market.winner.ok_or(PredictionMarketError::MarketNotResolved)?;
-    
+    // Only the winning leg(s) pay out, stakes on other options are lost
+    let stake = user_bet.winning_stake(&market.winning_options);
     require!(
-        user_bet.option_index == winner,
+        stake > 0,
         PredictionMarketError::NotWinner
     );
-    
-    // Calculate payout
-    let winning_pool = market.option_pools[winner as usize];
     require!(
-        winning_pool > 0,
+        stake <= market.outstanding_claims,
         PredictionMarketError::NothingToClaim
     );
     
-    let payout = (user_bet.amount as u128 * market.total_pool as u128 / winning_pool as u128) as u64;
-    
-    // Transfer winnings from market PDA to user
-    let market_key = market.key();
-    let market_id_bytes = market.market_id.to_le_bytes();
-    let seeds = &[
-        b"market",
-        market.creator.as_ref(),
-        market_id_bytes.as_ref(),
-        &[market.bump],
-    ];
-    let signer_seeds = &[&seeds[..]];
-    
-    **market.to_account_info().try_borrow_mut_lamports()? -= payout;
-    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += payout;
+    let payout = market.calculate_payout(stake)?;
+    
+    market.outstanding_claims -= stake;
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.pay_out(market, user_token_account, payout)?;
+    } else {
+        // Transfer winnings from market PDA to user, failing if the market
+        // cannot cover the payout and stay rent-exempt
+        transfer_lamports(
+            &market.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            payout,
+            &Rent::get()?,
+        )?;
+    }
     
     emit!(WinningsClaimed {
         market: market.key(),
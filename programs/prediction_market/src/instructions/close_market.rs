@@ -0,0 +1,128 @@
+// programs/prediction_market/src/instructions/close_market.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump,
+        has_one = creator @ PredictionMarketError::Unauthorized,
+        close = creator
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump,
+        close = creator
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    /// CHECK: rent recipient, checked against `market.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    // SPL collateral; leave out for a SOL market. Writable so withheld
+    // transfer fees can be harvested before the vault is closed
+    #[account(mut)]
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Receives the vault balance of a creator-funded (LMSR) market
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn handler(ctx: Context<CloseMarket>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+    
+    require!(
+        market.is_closable(ctx.accounts.config.claim_window, clock.unix_timestamp),
+        PredictionMarketError::MarketNotClosable
+    );
+    
+    // Everything above rent (unclaimed winnings, rounding dust, uncollected
+    // commission) goes to the treasury, rent goes back to the creator.
+    // A creator-funded market's balance goes back to the creator instead.
+    let rent = Rent::get()?;
+    let treasury = ctx.accounts.treasury.to_account_info();
+    let creator_funded = market.subsidy > 0;
+    let mut swept = 0u64;
+    
+    // Token markets: the vault balance goes to the treasury (or creator) token account
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    if let Some(token_vault) = &token_vault {
+        let creator = ctx.accounts.creator.to_account_info();
+        if creator_funded {
+            let creator_token_account = token_vault
+                .token_account(&ctx.accounts.creator_token_account, market.creator)?;
+            token_vault.sweep_and_close(market, creator_token_account, &creator)?;
+        } else {
+            let treasury_token_account = token_vault.token_account(
+                &ctx.accounts.treasury_token_account,
+                ctx.accounts.treasury.key(),
+            )?;
+            swept = token_vault.sweep_and_close(market, treasury_token_account, &creator)?;
+        }
+    }
+    
+    // SOL held by a creator-funded market goes to the creator with the rent
+    let mut accounts = vec![ctx.accounts.fee_vault.to_account_info()];
+    if !creator_funded {
+        accounts.push(market.to_account_info());
+    }
+    for info in accounts {
+        swept = swept
+            .checked_add(sweep_to_treasury(&info, &treasury, &rent)?)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    
+    emit!(MarketClosed {
+        market: market.key(),
+        creator: market.creator,
+        outstanding_claims: market.outstanding_claims,
+        swept_to_treasury: swept,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Accounts will be closed automatically due to close = creator
+    Ok(())
+}
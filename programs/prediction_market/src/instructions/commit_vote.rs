@@ -0,0 +1,110 @@
+// programs/prediction_market/src/instructions/commit_vote.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CommitVote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + ResolutionVotes::INIT_SPACE,
+        seeds = [
+            ResolutionVotes::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump
+    )]
+    pub votes: Account<'info, ResolutionVotes>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Members commit `hash(option || salt)` so later voters cannot copy earlier ones
+pub fn handler(ctx: Context<CommitVote>, commitment: [u8; 32]) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = &ctx.accounts.clock;
+    
+    // Validations
+    require!(
+        market.resolution_mode == ResolutionMode::Committee,
+        PredictionMarketError::WrongResolutionMode
+    );
+    
+    require!(
+        market.phase == MarketPhase::Betting || market.phase == MarketPhase::Resolving,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    match market.voting_stage(clock.unix_timestamp) {
+        VotingStage::Betting => return err!(PredictionMarketError::MarketNotEnded),
+        VotingStage::Commit => {}
+        VotingStage::Reveal | VotingStage::Tally => {
+            return err!(PredictionMarketError::CommitPhaseClosed)
+        }
+    }
+    
+    let member_index = market
+        .committee_index(&ctx.accounts.member.key())
+        .ok_or(PredictionMarketError::NotCommitteeMember)?;
+    
+    // First commitment opens the ballot at the current bond
+    let votes = &mut ctx.accounts.votes;
+    if votes.commitments.is_empty() {
+        votes.market = market.key();
+        votes.opened_by = ctx.accounts.member.key();
+        votes.bond = ctx.accounts.config.resolution_bond;
+        votes.commitments = vec![None; market.committee.len()];
+        votes.votes = vec![None; market.committee.len()];
+        votes.tallies = vec![0; market.options.len()];
+        votes.bump = ctx.bumps.votes;
+    }
+    
+    votes.commit(member_index, commitment)?;
+    
+    // Bond is returned on reveal and slashed otherwise
+    let bond = votes.bond;
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.member.to_account_info(),
+            to: ctx.accounts.votes.to_account_info(),
+        },
+    );
+    transfer(cpi_context, bond)?;
+    
+    // No more bets once the committee has started voting
+    market.phase = MarketPhase::Resolving;
+    
+    emit!(VoteCommitted {
+        market: market.key(),
+        member: ctx.accounts.member.key(),
+        bond,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
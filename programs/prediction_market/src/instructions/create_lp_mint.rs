@@ -0,0 +1,48 @@
+// programs/prediction_market/src/instructions/create_lp_mint.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CreateLpMint<'info> {
+    #[account(
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            AmmPool::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump,
+        mint::decimals = market.share_decimals(),
+        mint::authority = market,
+        mint::token_program = share_token_program
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub share_token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless: anyone can pay for a CPMM market's LP token mint
+pub fn handler(ctx: Context<CreateLpMint>) -> Result<()> {
+    require!(
+        ctx.accounts.market.amm.is_some(),
+        PredictionMarketError::WrongPricingMode
+    );
+    
+    Ok(())
+}
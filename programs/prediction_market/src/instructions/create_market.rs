@@ -1,13 +1,15 @@
 // programs/prediction_market/instructions/create_market.rs
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke;
-use anchor_lang::solana_program::system_instruction;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
 use crate::constants::*;
+use crate::collateral::*;
+use crate::lmsr;
+use crate::oracle::load_price;
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
@@ -25,12 +27,60 @@ pub struct CreateMarket<'info> {
     )]
     pub market: Account<'info, Market>,
     
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
     #[account(mut)]
     pub creator: Signer<'info>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        address = config.treasury @ PredictionMarketError::InvalidTreasury
+    )]
     pub platform: SystemAccount<'info>,
     
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            TokenVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = market,
+        token::token_program = token_program
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Pays the LMSR subsidy of a token market
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// CHECK: price feed of an oracle market; must be owned by the platform's
+    /// oracle program, decoded by the oracle adapter
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -40,8 +90,11 @@ pub fn handler(
     question: String,
     options: Vec<String>,
     end_time: i64,
+    resolution: ResolutionParams,
+    pricing: PricingMode,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
+    let config = &ctx.accounts.config;
     let clock = Clock::get()?;
     
     // Validations
@@ -69,14 +122,143 @@ pub fn handler(
     
     let duration = end_time - clock.unix_timestamp;
     require!(
-        duration >= MIN_MARKET_DURATION,
+        duration >= config.min_market_duration,
         PredictionMarketError::MarketTooShort
     );
     require!(
-        duration <= MAX_MARKET_DURATION,
+        duration <= config.max_market_duration,
         PredictionMarketError::MarketTooLong
     );
     
+    let ResolutionParams {
+        mode: resolution_mode,
+        tie_policy,
+        scoring_weights,
+        resolver,
+        oracle,
+        committee,
+        committee_threshold,
+    } = resolution;
+    match resolution_mode {
+        ResolutionMode::Algorithmic
+        | ResolutionMode::ManualResolver
+        | ResolutionMode::Optimistic => require!(
+            oracle.is_none() && committee.is_empty(),
+            PredictionMarketError::WrongResolutionMode
+        ),
+        ResolutionMode::Oracle => require!(
+            oracle.is_some() && committee.is_empty(),
+            PredictionMarketError::InvalidOracleAccount
+        ),
+        ResolutionMode::Committee => require!(
+            oracle.is_none(),
+            PredictionMarketError::WrongResolutionMode
+        ),
+    }
+    
+    require!(
+        scoring_weights.total() == 100
+            && scoring_weights.is_within(&config.min_scoring_weights, &config.max_scoring_weights),
+        PredictionMarketError::InvalidScoringWeights
+    );
+    
+    // M-of-N: distinct members, 1 <= M <= N
+    if resolution_mode == ResolutionMode::Committee {
+        require!(
+            committee.len() <= MAX_COMMITTEE_MEMBERS
+                && committee_threshold > 0
+                && committee_threshold as usize <= committee.len(),
+            PredictionMarketError::InvalidCommittee
+        );
+        for (i, member) in committee.iter().enumerate() {
+            require!(
+                !committee[..i].contains(member),
+                PredictionMarketError::InvalidCommittee
+            );
+        }
+    }
+    
+    // Oracle markets are binary: option 0 if the price condition holds, option 1 otherwise
+    if let Some(oracle) = &oracle {
+        require!(
+            options.len() == 2,
+            PredictionMarketError::InvalidOptionCount
+        );
+        require!(
+            oracle.feed != Pubkey::default() && oracle.max_staleness > 0,
+            PredictionMarketError::InvalidOracleAccount
+        );
+        
+        // The feed must be a live account of the accepted oracle program
+        let price_feed = ctx.accounts.price_feed
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidOracleAccount)?;
+        require_keys_eq!(
+            price_feed.key(),
+            oracle.feed,
+            PredictionMarketError::InvalidOracleAccount
+        );
+        let price = load_price(&oracle.source, price_feed, &config.oracle_program)?;
+        require!(
+            price.expo == oracle.expo,
+            PredictionMarketError::OracleExponentMismatch
+        );
+    }
+    
+    // LMSR: the creator funds the worst-case loss b * ln(n) up front.
+    // CPMM: binary markets only, liquidity providers fund the pool later
+    let subsidy = match &pricing {
+        PricingMode::Lmsr { liquidity } => {
+            require!(
+                *liquidity > 0,
+                PredictionMarketError::InvalidLiquidity
+            );
+            // Every trade evaluates the cost function twice and the prices once
+            require!(
+                options.len() <= MAX_LMSR_OPTIONS,
+                PredictionMarketError::InvalidOptionCount
+            );
+            lmsr::subsidy(*liquidity, options.len())?
+        }
+        PricingMode::Cpmm { fee_bps } => {
+            require!(
+                options.len() == 2,
+                PredictionMarketError::InvalidOptionCount
+            );
+            require!(
+                *fee_bps <= MAX_SWAP_FEE_BPS,
+                PredictionMarketError::InvalidSwapFee
+            );
+            0
+        }
+        PricingMode::Parimutuel => 0,
+    };
+    
+    // Minimums are configured with TOKEN_AMOUNT_DECIMALS, stored per mint
+    let collateral = match (&ctx.accounts.collateral_mint, &ctx.accounts.vault) {
+        (Some(mint), Some(vault)) => {
+            check_mint_extensions(&mint.to_account_info())?;
+            // LMSR and CPMM trades must deliver exactly the quoted cost
+            require!(
+                pricing == PricingMode::Parimutuel || !has_transfer_fee(&mint.to_account_info())?,
+                PredictionMarketError::UnsupportedMintExtension
+            );
+            Some(Collateral {
+                mint: mint.key(),
+                vault: vault.key(),
+                decimals: mint.decimals,
+                min_bet: scale_to_decimals(config.min_token_bet, TOKEN_AMOUNT_DECIMALS, mint.decimals)?,
+                min_velocity: scale_to_decimals(
+                    config.min_token_velocity,
+                    TOKEN_AMOUNT_DECIMALS,
+                    mint.decimals,
+                )?,
+            })
+        }
+        (None, None) => None,
+        _ => return err!(PredictionMarketError::MissingCollateralAccounts),
+    };
+    
     // Platform fee
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
@@ -85,11 +267,15 @@ pub fn handler(
             to: ctx.accounts.platform.to_account_info(),
         },
     );
-    transfer(cpi_context, CREATE_MARKET_FEE)?;
+    transfer(cpi_context, config.create_market_fee)?;
     
     // Market initialization
     market.creator = ctx.accounts.creator.key();
     market.market_id = market_id;
+    market.resolver = resolver.unwrap_or(market.creator);
+    market.collateral = collateral;
+    market.pricing = pricing.clone();
+    market.subsidy = subsidy;
     market.question = question;
     market.options = options.clone();
     market.start_time = clock.unix_timestamp;
@@ -100,21 +286,90 @@ pub fn handler(
     market.option_pools = vec![0u64; options.len()];
     market.total_pool = 0;
     market.total_fees = 0;
+    market.outstanding_claims = 0;
+    market.share_collateral = 0;
+    market.share_supply = vec![0u64; options.len()];
+    market.amm = match pricing {
+        PricingMode::Cpmm { .. } => Some(AmmPool { reserves: [0, 0], lp_supply: 0 }),
+        _ => None,
+    };
     
     market.leading_option = None;
     market.leading_since = None;
+    market.leadership_seconds = vec![0i64; options.len()];
+    market.pool_updated_at = vec![0i64; options.len()];
+    market.option_bettors = vec![0u32; options.len()];
+    
+    market.resolution_mode = resolution_mode.clone();
+    market.tie_policy = tie_policy.clone();
+    market.scoring_weights = scoring_weights;
+    market.oracle = oracle;
+    market.committee = committee;
+    market.committee_threshold = committee_threshold;
+    
+    // Committee votes are committed, then revealed, after the market ends
+    if market.resolution_mode == ResolutionMode::Committee {
+        market.commit_deadline = end_time
+            .checked_add(config.commit_window)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.reveal_deadline = market.commit_deadline
+            .checked_add(config.reveal_window)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    } else {
+        market.commit_deadline = 0;
+        market.reveal_deadline = 0;
+    }
     
     market.phase = MarketPhase::Betting;
     market.winner = None;
+    market.winning_options = Vec::new();
     market.paused = false;
     market.bump = ctx.bumps.market;
     
+    if subsidy > 0 {
+        let token_vault = TokenVault::load(
+            market,
+            &ctx.accounts.collateral_mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        
+        if let Some(token_vault) = &token_vault {
+            let creator_token_account = token_vault.token_account(
+                &ctx.accounts.creator_token_account,
+                ctx.accounts.creator.key(),
+            )?;
+            token_vault.deposit(creator_token_account, &ctx.accounts.creator.to_account_info(), subsidy)?;
+        } else {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: market.to_account_info(),
+                },
+            );
+            transfer(cpi_context, subsidy)?;
+        }
+    }
+    
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    fee_vault.market = market.key();
+    fee_vault.total_withdrawn = 0;
+    fee_vault.bump = ctx.bumps.fee_vault;
+    
     emit!(MarketCreated {
         market: market.key(),
         creator: market.creator,
+        resolver: market.resolver,
         market_id,
         end_time,
         options_count: options.len() as u8,
+        resolution_mode,
+        tie_policy,
+        scoring_weights,
+        collateral_mint: market.collateral.as_ref().map(|collateral| collateral.mint),
+        pricing,
+        subsidy,
     });
     
     Ok(())
@@ -0,0 +1,51 @@
+// programs/prediction_market/src/instructions/create_share_mint.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+use crate::state::*;
+use crate::errors::*;
+use crate::shares::*;
+
+#[derive(Accounts)]
+#[instruction(option_index: u8)]
+pub struct CreateShareMint<'info> {
+    #[account(
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            OutcomeShares::SEED_PREFIX,
+            market.key().as_ref(),
+            [option_index].as_ref()
+        ],
+        bump,
+        mint::decimals = market.share_decimals(),
+        mint::authority = market,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub share_token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless: anyone can pay for an option's share mint
+pub fn handler(ctx: Context<CreateShareMint>, option_index: u8) -> Result<()> {
+    require!(
+        (option_index as usize) < ctx.accounts.market.options.len(),
+        PredictionMarketError::InvalidOptionIndex
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// programs/prediction_market/src/instructions/dispute_outcome.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            ResolutionProposal::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = proposal.bump,
+        has_one = market
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Disputed proposals wait for the admin to settle them
+pub fn handler(ctx: Context<DisputeOutcome>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    
+    require!(
+        ctx.accounts.proposal.disputer.is_none(),
+        PredictionMarketError::AlreadyDisputed
+    );
+    
+    require!(
+        ctx.accounts.proposal.can_dispute(clock.unix_timestamp),
+        PredictionMarketError::DisputeWindowClosed
+    );
+    
+    // The disputer matches the proposer's bond
+    let bond = ctx.accounts.proposal.bond;
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.disputer.to_account_info(),
+            to: ctx.accounts.proposal.to_account_info(),
+        },
+    );
+    transfer(cpi_context, bond)?;
+    
+    // Past this deadline anyone can void the market and return both bonds
+    let settlement_deadline = clock.unix_timestamp
+        .checked_add(ctx.accounts.config.settlement_timeout)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.disputer = Some(ctx.accounts.disputer.key());
+    proposal.disputed_at = Some(clock.unix_timestamp);
+    proposal.settlement_deadline = Some(settlement_deadline);
+    
+    emit!(OutcomeDisputed {
+        market: ctx.accounts.market.key(),
+        disputer: ctx.accounts.disputer.key(),
+        proposed_option: proposal.proposed_option,
+        bond,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,120 @@
+// programs/prediction_market/src/instructions/expire_dispute.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::transfer_lamports;
+use crate::instructions::resolve_market::void_market;
+
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump,
+        has_one = creator @ PredictionMarketError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    // Proposer bond and rent go back to the proposer
+    #[account(
+        mut,
+        seeds = [
+            ResolutionProposal::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    /// CHECK: bound to the proposal, only receives the bond back
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    /// CHECK: checked against the proposal, only receives the bond back
+    #[account(mut)]
+    pub disputer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: only receives the market rent if the market is voided
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless: a dispute the admin never settles voids the market
+pub fn handler(ctx: Context<ExpireDispute>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let clock = &ctx.accounts.clock;
+    
+    let disputer = proposal.disputer.ok_or(PredictionMarketError::NotDisputed)?;
+    require_keys_eq!(
+        ctx.accounts.disputer.key(),
+        disputer,
+        PredictionMarketError::InvalidBondRecipient
+    );
+    
+    require!(
+        proposal.can_expire(clock.unix_timestamp),
+        PredictionMarketError::SettlementPending
+    );
+    
+    require!(
+        ctx.accounts.market.phase == MarketPhase::Resolving,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    // Neither side was proven right, so each bond goes back to its owner
+    let bond = proposal.bond;
+    let rent = Rent::get()?;
+    transfer_lamports(
+        &ctx.accounts.proposal.to_account_info(),
+        &ctx.accounts.disputer.to_account_info(),
+        bond,
+        &rent,
+    )?;
+    
+    emit!(DisputeExpired {
+        market: ctx.accounts.market.key(),
+        proposer: ctx.accounts.proposer.key(),
+        disputer,
+        bond,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    void_market(
+        &mut ctx.accounts.market,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        VoidReason::Unsettled,
+        clock.unix_timestamp,
+    )
+}
@@ -0,0 +1,167 @@
+// programs/prediction_market/src/instructions/finalize_committee_vote.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::instructions::resolve_market::{resolve_or_void, void_market};
+
+#[derive(Accounts)]
+pub struct FinalizeCommitteeVote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump,
+        has_one = creator @ PredictionMarketError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    // Absent when no member ever committed
+    #[account(
+        mut,
+        seeds = [
+            ResolutionVotes::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = votes.bump,
+        has_one = market,
+        has_one = opened_by
+    )]
+    pub votes: Option<Account<'info, ResolutionVotes>>,
+    
+    /// CHECK: bound to the ballot, only receives its rent
+    #[account(mut)]
+    pub opened_by: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: only receives the market rent if the market is voided
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless: tallies the revealed votes once the reveal phase is over
+pub fn handler(ctx: Context<FinalizeCommitteeVote>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = &ctx.accounts.clock;
+    
+    require!(
+        market.resolution_mode == ResolutionMode::Committee,
+        PredictionMarketError::WrongResolutionMode
+    );
+    
+    require!(
+        market.voting_stage(clock.unix_timestamp) == VotingStage::Tally,
+        PredictionMarketError::VotingInProgress
+    );
+    
+    let treasury = ctx.accounts.treasury.to_account_info();
+    let creator = ctx.accounts.creator.to_account_info();
+    
+    // The first commitment moves the market to Resolving, so a market still
+    // in Betting never opened a ballot and has no quorum
+    let (Some(votes), Some(opened_by)) = (&ctx.accounts.votes, &ctx.accounts.opened_by) else {
+        require!(
+            market.phase == MarketPhase::Betting,
+            PredictionMarketError::MarketAlreadyResolved
+        );
+        
+        emit!(CommitteeResolved {
+            market: market.key(),
+            winning_option: None,
+            tallies: vec![0; market.options.len()],
+            votes_cast: 0,
+            threshold: market.committee_threshold,
+            unrevealed: 0,
+            slashed: 0,
+        });
+        
+        return void_market(
+            market,
+            &ctx.accounts.fee_vault,
+            &treasury,
+            &creator,
+            VoidReason::NoQuorum,
+            clock.unix_timestamp,
+        );
+    };
+    
+    require!(
+        market.phase == MarketPhase::Resolving,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    // Bonds of members who never revealed go to the treasury
+    let unrevealed = votes.unrevealed();
+    let slashed = votes.bond
+        .checked_mul(unrevealed as u64)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    if slashed > 0 {
+        let rent = Rent::get()?;
+        transfer_lamports(
+            &votes.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            slashed,
+            &rent,
+        )?;
+    }
+    
+    let winner = votes.decided_option(market.committee_threshold);
+    
+    emit!(CommitteeResolved {
+        market: market.key(),
+        winning_option: winner,
+        tallies: votes.tallies.clone(),
+        votes_cast: votes.votes_cast(),
+        threshold: market.committee_threshold,
+        unrevealed,
+        slashed,
+    });
+    
+    match winner {
+        Some(winner) => resolve_or_void(
+            market,
+            &ctx.accounts.fee_vault,
+            &treasury,
+            &creator,
+            &[winner],
+            clock.unix_timestamp,
+        )?,
+        None => void_market(
+            market,
+            &ctx.accounts.fee_vault,
+            &treasury,
+            &creator,
+            VoidReason::NoQuorum,
+            clock.unix_timestamp,
+        )?,
+    }
+    
+    // Ballot rent goes back to the member who opened it. Closed here rather
+    // than with `close`, which would require `opened_by` even without a ballot
+    votes.close(opened_by.to_account_info())
+}
@@ -0,0 +1,94 @@
+// programs/prediction_market/src/instructions/finalize_resolution.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::resolve_market::resolve_or_void;
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump,
+        has_one = creator @ PredictionMarketError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    // Bond and rent go back to the proposer
+    #[account(
+        mut,
+        seeds = [
+            ResolutionProposal::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    /// CHECK: bound to the proposal, only receives the bond back
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: only receives the market rent if the market is voided
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless: an undisputed proposal stands once the window has passed
+pub fn handler(ctx: Context<FinalizeResolution>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let clock = &ctx.accounts.clock;
+    
+    require!(
+        proposal.disputer.is_none(),
+        PredictionMarketError::AlreadyDisputed
+    );
+    
+    require!(
+        proposal.can_finalize(clock.unix_timestamp),
+        PredictionMarketError::DisputeWindowOpen
+    );
+    
+    require!(
+        ctx.accounts.market.phase == MarketPhase::Resolving,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    resolve_or_void(
+        &mut ctx.accounts.market,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &[proposal.proposed_option],
+        clock.unix_timestamp,
+    )
+}
@@ -0,0 +1,112 @@
+// programs/prediction_market/src/instructions/mint_complete_set.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::collateral::*;
+use crate::shares::*;
+
+#[derive(Accounts)]
+pub struct MintCompleteSet<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub share_token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Remaining accounts: share mint and user share account of every option (see `OutcomeShares`)
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintCompleteSet<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    require!(
+        market.is_active(),
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        amount > 0,
+        PredictionMarketError::InvalidShareAmount
+    );
+    
+    let shares = OutcomeShares::load(market, ctx.remaining_accounts)?;
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    // One unit of collateral backs one share of every option
+    let amount = match &token_vault {
+        Some(token_vault) => {
+            let user_token_account = token_vault.token_account(
+                &ctx.accounts.user_token_account,
+                ctx.accounts.user.key(),
+            )?;
+            token_vault.deposit(user_token_account, &ctx.accounts.user.to_account_info(), amount)?
+        }
+        None => {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: market.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            amount
+        }
+    };
+    
+    market.share_collateral = market.share_collateral
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    for supply in market.share_supply.iter_mut() {
+        *supply = supply
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    
+    shares.mint(market, &ctx.accounts.share_token_program, amount)?;
+    
+    emit!(CompleteSetMinted {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        share_collateral: market.share_collateral,
+    });
+    
+    Ok(())
+}
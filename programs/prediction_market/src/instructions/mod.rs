@@ -3,21 +3,100 @@
 // ---------- modules ----------
 pub mod create_market;
 pub mod place_bet;
+pub mod withdraw_bet;
+pub mod buy_shares;
+pub mod sell_shares;
+pub mod create_lp_mint;
+pub mod add_liquidity;
+pub mod withdraw_liquidity;
 pub mod resolve_market;
+pub mod resolve_with_oracle;
+pub mod propose_outcome;
+pub mod dispute_outcome;
+pub mod finalize_resolution;
+pub mod expire_dispute;
+pub mod commit_vote;
+pub mod reveal_vote;
+pub mod finalize_committee_vote;
+pub mod create_share_mint;
+pub mod mint_complete_set;
+pub mod redeem_complete_set;
+pub mod redeem_shares;
 pub mod claim_winnings;
+pub mod cancel_market;
+pub mod refund_bet;
+pub mod close_market;
 pub mod admin; 
 
 // ---------- re export accounts structs  ----------
 pub use create_market::CreateMarket;
 pub use place_bet::PlaceBet;
+pub use withdraw_bet::WithdrawBet;
+pub use buy_shares::BuyShares;
+pub use sell_shares::SellShares;
+pub use create_lp_mint::CreateLpMint;
+pub use add_liquidity::AddLiquidity;
+pub use withdraw_liquidity::WithdrawLiquidity;
 pub use resolve_market::ResolveMarket;
+pub use resolve_with_oracle::ResolveWithOracle;
+pub use propose_outcome::ProposeOutcome;
+pub use dispute_outcome::DisputeOutcome;
+pub use finalize_resolution::FinalizeResolution;
+pub use expire_dispute::ExpireDispute;
+pub use commit_vote::CommitVote;
+pub use reveal_vote::RevealVote;
+pub use finalize_committee_vote::FinalizeCommitteeVote;
+pub use create_share_mint::CreateShareMint;
+pub use mint_complete_set::MintCompleteSet;
+pub use redeem_complete_set::RedeemCompleteSet;
+pub use redeem_shares::RedeemShares;
 pub use claim_winnings::ClaimWinnings;
+pub use cancel_market::CancelMarket;
+pub use refund_bet::RefundBet;
+pub use close_market::CloseMarket;
 pub use admin::EmergencyPause;  
+pub use admin::WithdrawFees;
+pub use admin::InitializeConfig;
+pub use admin::UpdateConfig;
+pub use admin::ProposeAdmin;
+pub use admin::AcceptAdmin;
+pub use admin::RenounceAdmin;
+pub use admin::SettleDispute;
+pub use admin::UpdateCommitteeQuorum;
 
 // english: These are used for Anchor's client-side code generation
 // english: required for Anchor's client-side code generation (macro usage, not visible in external API)
 pub(crate) use create_market::__client_accounts_create_market;
 pub(crate) use place_bet::__client_accounts_place_bet;
+pub(crate) use withdraw_bet::__client_accounts_withdraw_bet;
+pub(crate) use buy_shares::__client_accounts_buy_shares;
+pub(crate) use sell_shares::__client_accounts_sell_shares;
+pub(crate) use create_lp_mint::__client_accounts_create_lp_mint;
+pub(crate) use add_liquidity::__client_accounts_add_liquidity;
+pub(crate) use withdraw_liquidity::__client_accounts_withdraw_liquidity;
 pub(crate) use resolve_market::__client_accounts_resolve_market;
+pub(crate) use resolve_with_oracle::__client_accounts_resolve_with_oracle;
+pub(crate) use propose_outcome::__client_accounts_propose_outcome;
+pub(crate) use dispute_outcome::__client_accounts_dispute_outcome;
+pub(crate) use finalize_resolution::__client_accounts_finalize_resolution;
+pub(crate) use expire_dispute::__client_accounts_expire_dispute;
+pub(crate) use commit_vote::__client_accounts_commit_vote;
+pub(crate) use reveal_vote::__client_accounts_reveal_vote;
+pub(crate) use finalize_committee_vote::__client_accounts_finalize_committee_vote;
+pub(crate) use create_share_mint::__client_accounts_create_share_mint;
+pub(crate) use mint_complete_set::__client_accounts_mint_complete_set;
+pub(crate) use redeem_complete_set::__client_accounts_redeem_complete_set;
+pub(crate) use redeem_shares::__client_accounts_redeem_shares;
 pub(crate) use claim_winnings::__client_accounts_claim_winnings;
+pub(crate) use cancel_market::__client_accounts_cancel_market;
+pub(crate) use refund_bet::__client_accounts_refund_bet;
+pub(crate) use close_market::__client_accounts_close_market;
 pub(crate) use admin::emergency_pause::__client_accounts_emergency_pause;
+pub(crate) use admin::withdraw_fees::__client_accounts_withdraw_fees;
+pub(crate) use admin::initialize_config::__client_accounts_initialize_config;
+pub(crate) use admin::update_config::__client_accounts_update_config;
+pub(crate) use admin::propose_admin::__client_accounts_propose_admin;
+pub(crate) use admin::accept_admin::__client_accounts_accept_admin;
+pub(crate) use admin::renounce_admin::__client_accounts_renounce_admin;
+pub(crate) use admin::settle_dispute::__client_accounts_settle_dispute;
+pub(crate) use admin::update_committee_quorum::__client_accounts_update_committee_quorum;
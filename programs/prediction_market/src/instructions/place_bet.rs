@@ -1,13 +1,13 @@
 // programs/prediction_market/src/instructions/place_bet.rs
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke;
-use anchor_lang::solana_program::system_instruction;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
-use crate::constants::*;
+use crate::collateral::*;
 
 #[derive(Accounts)]
 pub struct PlaceBet<'info> {
@@ -35,9 +35,36 @@ pub struct PlaceBet<'info> {
     )]
     pub user_bet: Account<'info, UserBet>,
     
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
@@ -49,9 +76,15 @@ pub fn handler(
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let user_bet = &mut ctx.accounts.user_bet;
+    let config = &ctx.accounts.config;
     let clock = &ctx.accounts.clock;
     
     // Validations
+    require!(
+        market.pricing == PricingMode::Parimutuel,
+        PredictionMarketError::WrongPricingMode
+    );
+    
     require!(
         market.is_active(),
         PredictionMarketError::MarketNotActive
@@ -68,46 +101,88 @@ pub fn handler(
     );
     
     require!(
-        amount >= MIN_BET_AMOUNT,
+        amount >= market.min_bet(config),
         PredictionMarketError::BetTooSmall
     );
     
-    // Calculate commission
-    let elapsed_time = clock.unix_timestamp - market.start_time;
-    let total_duration = market.end_time - market.start_time;
-    let time_percentage = (elapsed_time as u64 * 100) / total_duration as u64;
-    
-    let commission_bps = if time_percentage <= EARLY_BET_THRESHOLD {
-        BASE_COMMISSION_BPS
-    } else {
-        LATE_COMMISSION_BPS
-    };
-    
-    let commission = (amount * commission_bps as u64) / 10_000;
-    let net_amount = amount - commission;
-    
     // Velocity limit check
-    let velocity_limit = calculate_velocity_limit(market.total_pool, clock.unix_timestamp, market.end_time);
+    let velocity_limit = calculate_velocity_limit(
+        config,
+        market.min_velocity(config),
+        market.total_pool,
+        clock.unix_timestamp,
+        market.end_time,
+    );
     require!(
         amount <= velocity_limit,
         PredictionMarketError::VelocityLimitExceeded
     );
     
-    // Transfer SOL to market PDA
-    invoke(
-        &system_instruction::transfer(
-            &ctx.accounts.user.key(),
-            &market.key(),
-            net_amount,
-        ),
-        &[
-            ctx.accounts.user.to_account_info(),
-            market.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
     )?;
     
-    // Update market state
+    // Stake and commission both go to the vault, commission is tracked in total_fees.
+    // Transfer-fee mints deliver less than `amount`, only what arrived is credited.
+    let amount = match &token_vault {
+        Some(token_vault) => {
+            let user_token_account = token_vault.token_account(
+                &ctx.accounts.user_token_account,
+                ctx.accounts.user.key(),
+            )?;
+            token_vault.deposit(user_token_account, &ctx.accounts.user.to_account_info(), amount)?
+        }
+        None => amount,
+    };
+    
+    // Calculate commission
+    let commission_bps = config.commission_bps(market.elapsed_percentage(clock.unix_timestamp));
+    
+    let commission = (amount as u128 * commission_bps as u128 / 10_000) as u64;
+    let net_amount = amount - commission;
+    require!(
+        net_amount > 0,
+        PredictionMarketError::BetTooSmall
+    );
+    
+    if token_vault.is_none() {
+        // Transfer SOL to market PDA
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: market.to_account_info(),
+                },
+            ),
+            net_amount,
+        )?;
+        
+        // Transfer commission to fee vault
+        if commission > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: ctx.accounts.fee_vault.to_account_info(),
+                    },
+                ),
+                commission,
+            )?;
+        }
+    }
+    
+    // Update market state; a first position on this option adds a bettor
+    if user_bet.position(option_index) == 0 {
+        market.option_bettors[option_index as usize] = market.option_bettors[option_index as usize]
+            .checked_add(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    
     market.option_pools[option_index as usize] = market.option_pools[option_index as usize]
         .checked_add(net_amount)
         .ok_or(PredictionMarketError::MathOverflow)?;
@@ -120,37 +195,36 @@ pub fn handler(
         .checked_add(commission)
         .ok_or(PredictionMarketError::MathOverflow)?;
     
+    market.record_pool_update(option_index, clock.unix_timestamp);
     // Update leader
     market.update_leader(clock);
     
-    // Update or create user bet
-    if user_bet.amount == 0 {
+    // Update or create user bet, one position per option so users can hedge
+    if user_bet.positions.is_empty() {
         user_bet.user = ctx.accounts.user.key();
         user_bet.market = market.key();
-        user_bet.option_index = option_index;
-        user_bet.amount = amount;
+        user_bet.positions = vec![0u64; market.options.len()];
+        user_bet.amount = 0;
+        user_bet.net_amount = 0;
         user_bet.placed_at = clock.unix_timestamp;
         user_bet.claimed = false;
         user_bet.bump = ctx.bumps.user_bet;
-    } else {
-        // User can only bet on same option
-        require!(
-            user_bet.option_index == option_index,
-            PredictionMarketError::InvalidOptionIndex
-        );
-        user_bet.amount = user_bet.amount
-            .checked_add(amount)
-            .ok_or(PredictionMarketError::MathOverflow)?;
     }
     
+    user_bet.positions[option_index as usize] = user_bet.positions[option_index as usize]
+        .checked_add(net_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    user_bet.amount = user_bet.amount
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    user_bet.net_amount = user_bet.net_amount
+        .checked_add(net_amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
     // Calculate simple odds for event
     let mut odds = vec![];
     for pool in &market.option_pools {
-        if market.total_pool > 0 {
-            odds.push((*pool * 100) / market.total_pool);
-        } else {
-            odds.push(0);
-        }
+        odds.push((*pool as u128 * 100).checked_div(market.total_pool as u128).unwrap_or(0) as u64);
     }
     
     emit!(BetPlaced {
@@ -175,17 +249,26 @@ pub fn handler(
     Ok(())
 }
 
-fn calculate_velocity_limit(total_pool: u64, current_time: i64, end_time: i64) -> u64 {
+fn calculate_velocity_limit(
+    config: &PlatformConfig,
+    min_velocity: u64,
+    total_pool: u64,
+    current_time: i64,
+    end_time: i64,
+) -> u64 {
     let time_remaining = (end_time - current_time).max(1) as u64;
     let hours_remaining = time_remaining / 3600;
     
     if total_pool == 0 || hours_remaining == 0 {
-        return MIN_VELOCITY;
+        return min_velocity;
     }
     
-    let dynamic_limit = (total_pool * VELOCITY_FACTOR) / 100 / hours_remaining.max(1).isqrt();
+    let dynamic_limit = total_pool as u128 * config.velocity_factor as u128
+        / 100
+        / hours_remaining.max(1).isqrt() as u128;
     
-    dynamic_limit.max(MIN_VELOCITY)
+    // A limit too large for u64 means no limit at all
+    u64::try_from(dynamic_limit).unwrap_or(u64::MAX).max(min_velocity)
 }
 
 
@@ -0,0 +1,111 @@
+// programs/prediction_market/src/instructions/propose_outcome.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ProposeOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + ResolutionProposal::INIT_SPACE,
+        seeds = [
+            ResolutionProposal::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Anyone can propose; a wrong proposal loses its bond to the disputer
+pub fn handler(ctx: Context<ProposeOutcome>, option_index: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let config = &ctx.accounts.config;
+    let clock = &ctx.accounts.clock;
+    
+    // Validations
+    require!(
+        market.resolution_mode == ResolutionMode::Optimistic,
+        PredictionMarketError::WrongResolutionMode
+    );
+    
+    require!(
+        market.phase == MarketPhase::Betting,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    require!(
+        clock.unix_timestamp >= market.end_time,
+        PredictionMarketError::MarketNotEnded
+    );
+    
+    require!(
+        (option_index as usize) < market.options.len(),
+        PredictionMarketError::InvalidOptionIndex
+    );
+    
+    let dispute_deadline = clock.unix_timestamp
+        .checked_add(config.dispute_window)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    // Bond is held by the proposal account until the outcome is final
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.proposer.to_account_info(),
+            to: ctx.accounts.proposal.to_account_info(),
+        },
+    );
+    transfer(cpi_context, config.resolution_bond)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.market = market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.proposed_option = option_index;
+    proposal.bond = config.resolution_bond;
+    proposal.proposed_at = clock.unix_timestamp;
+    proposal.dispute_deadline = dispute_deadline;
+    proposal.disputer = None;
+    proposal.disputed_at = None;
+    proposal.settlement_deadline = None;
+    proposal.bump = ctx.bumps.proposal;
+    
+    // No more bets or direct resolution while the proposal is pending
+    market.phase = MarketPhase::Resolving;
+    
+    emit!(OutcomeProposed {
+        market: market.key(),
+        proposer: proposal.proposer,
+        option_index,
+        bond: proposal.bond,
+        dispute_deadline,
+    });
+    
+    Ok(())
+}
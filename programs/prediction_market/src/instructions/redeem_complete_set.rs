@@ -0,0 +1,96 @@
+// programs/prediction_market/src/instructions/redeem_complete_set.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+use crate::shares::*;
+
+#[derive(Accounts)]
+pub struct RedeemCompleteSet<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub share_token_program: Program<'info, Token>,
+}
+
+// A complete set is always worth one unit of collateral, in any phase
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemCompleteSet<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    require!(
+        amount > 0,
+        PredictionMarketError::InvalidShareAmount
+    );
+    
+    let shares = OutcomeShares::load(market, ctx.remaining_accounts)?;
+    shares.burn(&ctx.accounts.user.to_account_info(), &ctx.accounts.share_token_program, amount)?;
+    
+    market.share_collateral = market.share_collateral
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::InsufficientMarketFunds)?;
+    for supply in market.share_supply.iter_mut() {
+        *supply = supply
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::InsufficientPosition)?;
+    }
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.pay_out(market, user_token_account, amount)?;
+    } else {
+        transfer_lamports(
+            &market.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            amount,
+            &Rent::get()?,
+        )?;
+    }
+    
+    emit!(CompleteSetRedeemed {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        share_collateral: market.share_collateral,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,120 @@
+// programs/prediction_market/src/instructions/redeem_shares.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+use crate::shares::*;
+
+#[derive(Accounts)]
+#[instruction(option_index: u8)]
+pub struct RedeemShares<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            OutcomeShares::SEED_PREFIX,
+            market.key().as_ref(),
+            [option_index].as_ref()
+        ],
+        bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = user
+    )]
+    pub user_share_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, token_interface::TokenInterface>>,
+    
+    pub share_token_program: Program<'info, Token>,
+}
+
+// Settled markets only: winning shares, or every share of a cancelled market,
+// split the remaining share collateral pro rata
+pub fn handler(ctx: Context<RedeemShares>, option_index: u8, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    require!(
+        market.phase == MarketPhase::Resolved || market.phase == MarketPhase::Cancelled,
+        PredictionMarketError::MarketNotResolved
+    );
+    
+    require!(
+        amount > 0,
+        PredictionMarketError::InvalidShareAmount
+    );
+    
+    // May be zero for a handful of shares; they are burned all the same
+    let payout = market.redeem_shares(option_index, amount)?;
+    
+    let cpi_context = CpiContext::new(
+        ctx.accounts.share_token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            from: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    burn(cpi_context, amount)?;
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.pay_out(market, user_token_account, payout)?;
+    } else {
+        transfer_lamports(
+            &market.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            payout,
+            &Rent::get()?,
+        )?;
+    }
+    
+    emit!(SharesRedeemed {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        option_index,
+        amount,
+        payout,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,189 @@
+// programs/prediction_market/src/instructions/refund_bet.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+use crate::lmsr;
+
+#[derive(Accounts)]
+pub struct RefundBet<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            UserBet::SEED_PREFIX,
+            user.key().as_ref(),
+            market.key().as_ref()
+        ],
+        bump = user_bet.bump,
+        has_one = user @ PredictionMarketError::Unauthorized,
+        close = user
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: only receives the market rent after the last refund
+    #[account(mut, address = market.creator @ PredictionMarketError::Unauthorized)]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market. Writable so withheld
+    // transfer fees can be harvested before the vault is closed
+    #[account(mut)]
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Receives the leftover fees after the last refund
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Receives what is left of the LMSR subsidy after the last refund
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn handler(ctx: Context<RefundBet>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_bet = &ctx.accounts.user_bet;
+    
+    require!(
+        market.phase == MarketPhase::Cancelled,
+        PredictionMarketError::MarketNotCancelled
+    );
+    
+    // Stake (or shares) counted in the pool
+    let stake = user_bet.net_amount;
+    
+    // LMSR shares are bought back at the current cost-function price,
+    // CPMM shares are worth 1/n of a complete set like outcome share tokens.
+    // Only a parimutuel stake paid commission, which sits in the fee vault
+    let (payout, commission) = match market.pricing {
+        PricingMode::Lmsr { liquidity } => (
+            lmsr::sell_refund(&market.option_pools, liquidity, &user_bet.positions)?,
+            0,
+        ),
+        PricingMode::Cpmm { .. } => (
+            user_bet.positions
+                .iter()
+                .enumerate()
+                .map(|(option_index, position)| market.share_payout(option_index as u8, *position))
+                .sum(),
+            0,
+        ),
+        PricingMode::Parimutuel => (stake, user_bet.commission()),
+    };
+    let refund = payout
+        .checked_add(commission)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    for (option_index, position) in user_bet.positions.iter().enumerate() {
+        market.option_pools[option_index] = market.option_pools[option_index]
+            .checked_sub(*position)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    market.total_pool = market.total_pool
+        .checked_sub(stake)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.total_fees = market.total_fees
+        .checked_sub(commission)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        // Stake and commission both sit in the vault
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.pay_out(market, user_token_account, refund)?;
+    } else {
+        let user_info = ctx.accounts.user.to_account_info();
+        let rent = Rent::get()?;
+        
+        transfer_lamports(&market.to_account_info(), &user_info, payout, &rent)?;
+        transfer_lamports(&ctx.accounts.fee_vault.to_account_info(), &user_info, commission, &rent)?;
+    }
+    
+    emit!(BetRefunded {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount: refund,
+    });
+    
+    // Last refund returns the market and fee vault rent to the creator,
+    // fees that are no longer refundable (exit penalties) go to the treasury
+    // and an LMSR subsidy back to the creator
+    if market.is_fully_refunded() {
+        if let Some(token_vault) = &token_vault {
+            let leftover_account = if market.subsidy > 0 {
+                token_vault.token_account(&ctx.accounts.creator_token_account, market.creator)?
+            } else {
+                token_vault.token_account(
+                    &ctx.accounts.treasury_token_account,
+                    ctx.accounts.treasury.key(),
+                )?
+            };
+            token_vault.sweep_and_close(
+                market,
+                leftover_account,
+                &ctx.accounts.creator.to_account_info(),
+            )?;
+        }
+        close_refunded_market(
+            market,
+            &ctx.accounts.fee_vault,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+    
+    // Account will be closed automatically due to close = user
+    Ok(())
+}
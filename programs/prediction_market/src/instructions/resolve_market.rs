@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct ResolveMarket<'info> {
@@ -18,11 +19,40 @@ pub struct ResolveMarket<'info> {
     )]
     pub market: Account<'info, Market>,
     
-    pub creator: Signer<'info>,
+    // Market's designated resolver, or the platform admin as an override
+    #[account(
+        constraint = market.can_resolve(&resolver.key(), &config.admin)
+            @ PredictionMarketError::Unauthorized
+    )]
+    pub resolver: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: only receives the market rent if the market is voided
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<ResolveMarket>) -> Result<()> {
+pub fn handler(ctx: Context<ResolveMarket>, winning_option: Option<u8>) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let clock = &ctx.accounts.clock;
     
@@ -37,59 +67,148 @@ pub fn handler(ctx: Context<ResolveMarket>) -> Result<()> {
         PredictionMarketError::MarketNotEnded
     );
     
-    require!(
-        market.total_pool > 0,
-        PredictionMarketError::NoBetsPlaced
-    );
+    // Manual markets take the real outcome from the resolver,
+    // algorithmic ones score the pools
+    let outcome = match (&market.resolution_mode, winning_option) {
+        (ResolutionMode::Algorithmic, None) => {
+            // Nothing to decide without at least two funded options
+            if market.funded_options() < 2 {
+                Err(if market.total_pool == 0 {
+                    VoidReason::NoBets
+                } else {
+                    VoidReason::OneSided
+                })
+            } else {
+                let tied = calculate_winner(market, clock)?;
+                market.apply_tie_policy(&tied).ok_or(VoidReason::Tie)
+            }
+        }
+        (ResolutionMode::ManualResolver, Some(option)) => {
+            require!(
+                (option as usize) < market.options.len(),
+                PredictionMarketError::InvalidOptionIndex
+            );
+            Ok(vec![option])
+        }
+        (ResolutionMode::Algorithmic, Some(_)) | (ResolutionMode::ManualResolver, None) => {
+            return err!(PredictionMarketError::InvalidWinningOption);
+        }
+        _ => return err!(PredictionMarketError::WrongResolutionMode),
+    };
     
-    // Calculate winner based on time-weighted score
-    let winner = calculate_winner(market, clock)?;
+    let treasury = ctx.accounts.treasury.to_account_info();
+    let creator = ctx.accounts.creator.to_account_info();
+    match outcome {
+        Ok(winners) => resolve_or_void(
+            market,
+            &ctx.accounts.fee_vault,
+            &treasury,
+            &creator,
+            &winners,
+            clock.unix_timestamp,
+        ),
+        // Void instead of resolving when there is no one to pay out
+        Err(reason) => void_market(
+            market,
+            &ctx.accounts.fee_vault,
+            &treasury,
+            &creator,
+            reason,
+            clock.unix_timestamp,
+        ),
+    }
+}
+
+/// Cancels a market that cannot be resolved meaningfully so bettors can use `refund_bet`.
+pub(crate) fn void_market<'info>(
+    market: &mut Account<'info, Market>,
+    fee_vault: &Account<'info, FeeVault>,
+    treasury: &AccountInfo<'info>,
+    creator: &AccountInfo<'info>,
+    reason: VoidReason,
+    now: i64,
+) -> Result<()> {
+    market.phase = MarketPhase::Cancelled;
+    market.resolution_time = Some(now);
     
-    // Update market state
-    market.phase = MarketPhase::Resolved;
-    market.winner = Some(winner);
-    market.resolution_time = Some(clock.unix_timestamp);
+    emit!(MarketVoided {
+        market: market.key(),
+        reason,
+        total_pool: market.total_pool,
+        resolution_time: now,
+    });
+    
+    // No one to refund: give the creator the rent back right away. Bets
+    // that were fully withdrawn are already closed, so no bet account is
+    // left behind. Otherwise the last `refund_bet` closes the accounts. Token markets
+    // need their token accounts for that and are closed by `close_market`.
+    if market.is_fully_refunded() && market.collateral.is_none() {
+        close_refunded_market(market, fee_vault, treasury, creator)?;
+    }
+    
+    Ok(())
+}
+
+/// Resolves on `winners` (several only for a split pot), or voids the
+/// market when nobody backed them.
+pub(crate) fn resolve_or_void<'info>(
+    market: &mut Account<'info, Market>,
+    fee_vault: &Account<'info, FeeVault>,
+    treasury: &AccountInfo<'info>,
+    creator: &AccountInfo<'info>,
+    winners: &[u8],
+    now: i64,
+) -> Result<()> {
+    let winning_pool: u64 = winners
+        .iter()
+        .map(|option| market.option_pools[*option as usize])
+        .sum();
+    if winning_pool == 0 {
+        let reason = if market.total_pool == 0 {
+            VoidReason::NoBets
+        } else {
+            VoidReason::NoWinningBets
+        };
+        return void_market(market, fee_vault, treasury, creator, reason, now);
+    }
     
-    let winning_pool = market.option_pools[winner as usize];
+    market.resolve(winners, now);
     
     emit!(MarketResolved {
         market: market.key(),
-        winning_option: winner,
+        winning_option: winners[0],
+        winning_options: winners.to_vec(),
         total_pool: market.total_pool,
         winning_pool,
-        resolution_time: clock.unix_timestamp,
+        resolution_time: now,
     });
     
     Ok(())
 }
 
-fn calculate_winner(market: &Market, clock: &Clock) -> Result<u8> {
-    let total_duration = market.end_time - market.start_time;
+/// Options sharing the best score; more than one means a tie.
+fn calculate_winner(market: &Market, clock: &Clock) -> Result<Vec<u8>> {
+    // Leadership only counts while the market was open
+    let scored_until = clock.unix_timestamp.min(market.end_time);
     let mut best_score = 0u128;
-    let mut winner = 0u8;
+    let mut winners = Vec::new();
     
     for (i, pool) in market.option_pools.iter().enumerate() {
         if *pool == 0 {
             continue;
         }
         
-        // score logic %70 time, %30 money
-        let time_score = if market.leading_option == Some(i as u8) {
-            let leadership_duration = clock.unix_timestamp - market.leading_since.unwrap_or(market.start_time);
-            let time_percentage = (leadership_duration as u128 * 100) / total_duration as u128;
-            time_percentage * 70
-        } else {
-            0
-        };
-        
-        let money_score = (*pool as u128 * 100 * 30) / market.total_pool as u128;
-        let total_score = time_score + money_score;
+        // Weighted time led, pool share and bettor share (see `ScoringWeights`)
+        let total_score = market.score(i as u8, scored_until);
         
         if total_score > best_score {
             best_score = total_score;
-            winner = i as u8;
+            winners.clear();
+            winners.push(i as u8);
+        } else if total_score == best_score {
+            winners.push(i as u8);
         }
     }
     
-    Ok(winner)
-}
\ No newline at end of file
+    Ok(winners)
+}
@@ -0,0 +1,127 @@
+// programs/prediction_market/src/instructions/resolve_with_oracle.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::oracle::*;
+use crate::instructions::resolve_market::void_market;
+
+#[derive(Accounts)]
+pub struct ResolveWithOracle<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump,
+        has_one = creator @ PredictionMarketError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: must be the feed bound to the market at creation, owned by the
+    /// platform's oracle program and decoded by the oracle adapter
+    #[account(
+        owner = config.oracle_program @ PredictionMarketError::InvalidOracleAccount,
+        constraint = market.oracle.as_ref().map(|oracle| oracle.feed) == Some(price_feed.key())
+            @ PredictionMarketError::InvalidOracleAccount
+    )]
+    pub price_feed: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = treasury @ PredictionMarketError::InvalidTreasury
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: only receives the market rent if the market is voided
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless: the outcome comes from the feed, not from the caller
+pub fn handler(ctx: Context<ResolveWithOracle>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = &ctx.accounts.clock;
+    
+    // Validations
+    require!(
+        market.phase == MarketPhase::Betting,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    require!(
+        clock.unix_timestamp >= market.end_time,
+        PredictionMarketError::MarketNotEnded
+    );
+    
+    require!(
+        market.resolution_mode == ResolutionMode::Oracle,
+        PredictionMarketError::WrongResolutionMode
+    );
+    
+    let oracle = market.oracle.clone().ok_or(PredictionMarketError::WrongResolutionMode)?;
+    let price = load_price(
+        &oracle.source,
+        &ctx.accounts.price_feed.to_account_info(),
+        &ctx.accounts.config.oracle_program,
+    )?;
+    
+    // The price must be published after the market closed and still be fresh
+    let winner = oracle.resolve_outcome(&price, market.end_time, clock.unix_timestamp)?;
+    
+    // Nobody backed the real outcome: refund everyone instead
+    if market.option_pools[winner as usize] == 0 {
+        let reason = if market.total_pool == 0 {
+            VoidReason::NoBets
+        } else {
+            VoidReason::NoWinningBets
+        };
+        return void_market(
+            market,
+            &ctx.accounts.fee_vault,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            reason,
+            clock.unix_timestamp,
+        );
+    }
+    
+    market.resolve(&[winner], clock.unix_timestamp);
+    
+    emit!(OracleResolved {
+        market: market.key(),
+        price_feed: oracle.feed,
+        price: price.price,
+        expo: price.expo,
+        publish_time: price.publish_time,
+    });
+    
+    emit!(MarketResolved {
+        market: market.key(),
+        winning_option: winner,
+        winning_options: vec![winner],
+        total_pool: market.total_pool,
+        winning_pool: market.option_pools[winner as usize],
+        resolution_time: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
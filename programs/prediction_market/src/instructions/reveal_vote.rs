@@ -0,0 +1,77 @@
+// programs/prediction_market/src/instructions/reveal_vote.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    #[account(
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            ResolutionVotes::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = votes.bump,
+        has_one = market
+    )]
+    pub votes: Account<'info, ResolutionVotes>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RevealVote>, option_index: u8, salt: [u8; 32]) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let votes = &mut ctx.accounts.votes;
+    let clock = &ctx.accounts.clock;
+    
+    require!(
+        market.voting_stage(clock.unix_timestamp) == VotingStage::Reveal,
+        PredictionMarketError::RevealPhaseClosed
+    );
+    
+    let member_index = market
+        .committee_index(&ctx.accounts.member.key())
+        .ok_or(PredictionMarketError::NotCommitteeMember)?;
+    
+    require!(
+        (option_index as usize) < market.options.len(),
+        PredictionMarketError::InvalidOptionIndex
+    );
+    
+    let tally = votes.reveal(member_index, option_index, &salt)?;
+    
+    // An honest reveal gets the bond back
+    let rent = Rent::get()?;
+    transfer_lamports(
+        &votes.to_account_info(),
+        &ctx.accounts.member.to_account_info(),
+        votes.bond,
+        &rent,
+    )?;
+    
+    emit!(VoteRevealed {
+        market: market.key(),
+        member: ctx.accounts.member.key(),
+        option_index,
+        tally,
+        threshold: market.committee_threshold,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,151 @@
+// programs/prediction_market/src/instructions/sell_shares.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+
+#[derive(Accounts)]
+pub struct SellShares<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            UserBet::SEED_PREFIX,
+            user.key().as_ref(),
+            market.key().as_ref()
+        ],
+        bump = user_bet.bump,
+        has_one = user @ PredictionMarketError::Unauthorized
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// LMSR and CPMM markets: sells `shares` back at the market maker's price, for at least `min_refund`
+pub fn handler(
+    ctx: Context<SellShares>,
+    option_index: u8,
+    shares: u64,
+    min_refund: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_bet = &mut ctx.accounts.user_bet;
+    let clock = &ctx.accounts.clock;
+    
+    // Validations
+    require!(
+        market.pricing != PricingMode::Parimutuel,
+        PredictionMarketError::WrongPricingMode
+    );
+    
+    require!(
+        market.is_active(),
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketEnded
+    );
+    
+    require!(
+        (option_index as usize) < market.options.len(),
+        PredictionMarketError::InvalidOptionIndex
+    );
+    
+    require!(
+        shares > 0 && shares <= user_bet.position(option_index),
+        PredictionMarketError::InsufficientPosition
+    );
+    
+    let refund = market.sell_refund(option_index, shares)?;
+    require!(
+        refund >= min_refund,
+        PredictionMarketError::SlippageExceeded
+    );
+    
+    // Update market state
+    market.option_pools[option_index as usize] -= shares;
+    market.total_pool = market.total_pool
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    market.record_pool_update(option_index, clock.unix_timestamp);
+    market.update_leader(clock);
+    
+    // Update user bet; closing a position removes the bettor from the option
+    user_bet.positions[option_index as usize] -= shares;
+    if user_bet.positions[option_index as usize] == 0 {
+        market.option_bettors[option_index as usize] -= 1;
+    }
+    user_bet.amount -= shares;
+    user_bet.net_amount = user_bet.amount;
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.pay_out(market, user_token_account, refund)?;
+    } else {
+        transfer_lamports(
+            &market.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            refund,
+            &Rent::get()?,
+        )?;
+    }
+    
+    emit!(SharesTraded {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        option_index,
+        shares,
+        collateral: refund,
+        bought: false,
+        prices_bps: market.prices_bps(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Selling every share leaves nothing to refund, see `withdraw_bet`
+    if user_bet.net_amount == 0 {
+        user_bet.close(ctx.accounts.user.to_account_info())?;
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,183 @@
+// programs/prediction_market/src/instructions/withdraw_bet.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+
+#[derive(Accounts)]
+pub struct WithdrawBet<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            UserBet::SEED_PREFIX,
+            user.key().as_ref(),
+            market.key().as_ref()
+        ],
+        bump = user_bet.bump,
+        has_one = user @ PredictionMarketError::Unauthorized
+    )]
+    pub user_bet: Account<'info, UserBet>,
+    
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<WithdrawBet>,
+    option_index: u8,
+    amount: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_bet = &mut ctx.accounts.user_bet;
+    let config = &ctx.accounts.config;
+    let clock = &ctx.accounts.clock;
+    
+    // Validations: shares are sold back through `sell_shares`, only a
+    // parimutuel stake carries the commission `UserBet::withdraw` splits off
+    match market.pricing {
+        PricingMode::Parimutuel => {}
+        PricingMode::Lmsr { .. } | PricingMode::Cpmm { .. } => {
+            return err!(PredictionMarketError::WrongPricingMode);
+        }
+    }
+    
+    require!(
+        market.is_active(),
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketEnded
+    );
+    
+    require!(
+        (option_index as usize) < market.options.len(),
+        PredictionMarketError::InvalidOptionIndex
+    );
+    
+    require!(
+        amount > 0 && amount <= user_bet.position(option_index),
+        PredictionMarketError::InsufficientPosition
+    );
+    
+    // Exit penalty, scaled by how late in the market the user leaves
+    let penalty_bps = config.exit_penalty_bps(market.elapsed_percentage(clock.unix_timestamp));
+    let penalty = (amount as u128 * penalty_bps as u128 / 10_000) as u64;
+    let payout = amount - penalty;
+    
+    // Update market state
+    market.option_pools[option_index as usize] = market.option_pools[option_index as usize]
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    market.total_pool = market.total_pool
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    market.total_fees = market.total_fees
+        .checked_add(penalty)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    market.record_pool_update(option_index, clock.unix_timestamp);
+    market.update_leader(clock);
+    
+    // Update user bet; the withdrawn stake's commission is no longer refundable.
+    // Closing a position removes the bettor from the option
+    user_bet.withdraw(option_index, amount)?;
+    if user_bet.position(option_index) == 0 {
+        market.option_bettors[option_index as usize] -= 1;
+    }
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        // Penalty stays in the vault as fees
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.pay_out(market, user_token_account, payout)?;
+    } else {
+        // Stake leaves the market PDA: payout to user, penalty to fee vault
+        let rent = Rent::get()?;
+        let market_info = market.to_account_info();
+        transfer_lamports(&market_info, &ctx.accounts.user.to_account_info(), payout, &rent)?;
+        transfer_lamports(&market_info, &ctx.accounts.fee_vault.to_account_info(), penalty, &rent)?;
+    }
+    
+    emit!(BetWithdrawn {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        option_index,
+        amount,
+        penalty,
+        payout,
+        new_pool_size: market.option_pools[option_index as usize],
+        timestamp: clock.unix_timestamp,
+    });
+    
+    if let Some(leader) = market.leading_option {
+        emit!(LeaderChanged {
+            market: market.key(),
+            new_leader: leader,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    // A full exit leaves nothing to refund, so the bet account is closed now
+    // instead of outliving a market that `void_market` closes right away
+    if user_bet.net_amount == 0 {
+        user_bet.close(ctx.accounts.user.to_account_info())?;
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// programs/prediction_market/src/instructions/withdraw_liquidity.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, burn, Burn, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use crate::collateral::*;
+use crate::cpmm;
+
+#[derive(Accounts)]
+pub struct WithdrawLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"market",
+            market.creator.as_ref(),
+            market.market_id.to_le_bytes().as_ref()
+        ],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [
+            AmmPool::SEED_PREFIX,
+            market.key().as_ref()
+        ],
+        bump
+    )]
+    pub lp_mint: Account<'info, token::Mint>,
+    
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = user
+    )]
+    pub user_lp_account: Account<'info, token::TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    // SPL collateral; leave out for a SOL market
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub share_token_program: Program<'info, Token>,
+}
+
+// Settled CPMM markets: burns LP tokens for their part of the pool's reserves,
+// paid like outcome shares (winning ones 1:1, cancelled markets 1/n per share)
+pub fn handler(ctx: Context<WithdrawLiquidity>, lp_tokens: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+    
+    require!(
+        market.phase == MarketPhase::Resolved || market.phase == MarketPhase::Cancelled,
+        PredictionMarketError::MarketNotResolved
+    );
+    
+    let pool = market.cpmm_pool()?;
+    let shares = cpmm::withdrawal(pool.reserves, pool.lp_supply, lp_tokens)?;
+    pool.reserves = [pool.reserves[0] - shares[0], pool.reserves[1] - shares[1]];
+    pool.lp_supply -= lp_tokens;
+    
+    let payout = market.share_payout(0, shares[0]) + market.share_payout(1, shares[1]);
+    
+    let cpi_context = CpiContext::new(
+        ctx.accounts.share_token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            from: ctx.accounts.user_lp_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    burn(cpi_context, lp_tokens)?;
+    
+    let token_vault = TokenVault::load(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    )?;
+    
+    if let Some(token_vault) = &token_vault {
+        let user_token_account = token_vault.token_account(
+            &ctx.accounts.user_token_account,
+            ctx.accounts.user.key(),
+        )?;
+        token_vault.pay_out(market, user_token_account, payout)?;
+    } else {
+        transfer_lamports(
+            &market.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            payout,
+            &Rent::get()?,
+        )?;
+    }
+    
+    emit!(LiquidityWithdrawn {
+        market: market.key(),
+        provider: ctx.accounts.user.key(),
+        lp_tokens,
+        shares,
+        payout,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
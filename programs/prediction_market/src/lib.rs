@@ -1,26 +1,72 @@
 // programs/prediction_market/src/lib.rs
+// Anchor's generated IDL handlers still call the deprecated `AccountInfo::realloc`.
+// `#[program]` emits them in a `__private` module at the crate root, outside
+// the program module, so the allow cannot be scoped any narrower than this.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
 declare_id!("wV5jwseh9fQfrdHUbxafCfGpvuWbQaNYqQaBJS8vuVa"); // Deploy sonrası değişecek
 
-pub const PLATFORM_ADMIN: Pubkey = pubkey!("wV5jwseh9fQfrdHUbxafCfGpvuWbQaNYqQaBJS8vuVa");
-
 pub mod instructions;
 pub mod state;
 pub mod errors;
 pub mod constants;
 pub mod events;
+pub mod utils;
+pub mod oracle;
+pub mod collateral;
+pub mod shares;
+pub mod lmsr;
+pub mod cpmm;
 
 use instructions::{
-    CreateMarket, PlaceBet, ResolveMarket, ClaimWinnings, EmergencyPause,
+    CreateMarket, PlaceBet, WithdrawBet, BuyShares, SellShares,
+    CreateLpMint, AddLiquidity, WithdrawLiquidity, ResolveMarket, ResolveWithOracle,
+    ProposeOutcome, DisputeOutcome, FinalizeResolution, ExpireDispute,
+    CommitVote, RevealVote, FinalizeCommitteeVote,
+    CreateShareMint, MintCompleteSet, RedeemCompleteSet, RedeemShares, ClaimWinnings,
+    CancelMarket, RefundBet, CloseMarket,
+    EmergencyPause, WithdrawFees, InitializeConfig, UpdateConfig,
+    ProposeAdmin, AcceptAdmin, RenounceAdmin, SettleDispute, UpdateCommitteeQuorum,
 };
+use state::{ConfigParams, PricingMode, ResolutionParams};
 
 pub(crate) use instructions::{
     __client_accounts_create_market,
     __client_accounts_place_bet,
+    __client_accounts_withdraw_bet,
+    __client_accounts_buy_shares,
+    __client_accounts_sell_shares,
+    __client_accounts_create_lp_mint,
+    __client_accounts_add_liquidity,
+    __client_accounts_withdraw_liquidity,
     __client_accounts_resolve_market,
+    __client_accounts_resolve_with_oracle,
+    __client_accounts_propose_outcome,
+    __client_accounts_dispute_outcome,
+    __client_accounts_finalize_resolution,
+    __client_accounts_expire_dispute,
+    __client_accounts_commit_vote,
+    __client_accounts_reveal_vote,
+    __client_accounts_finalize_committee_vote,
+    __client_accounts_create_share_mint,
+    __client_accounts_mint_complete_set,
+    __client_accounts_redeem_complete_set,
+    __client_accounts_redeem_shares,
     __client_accounts_claim_winnings,
+    __client_accounts_cancel_market,
+    __client_accounts_refund_bet,
+    __client_accounts_close_market,
     __client_accounts_emergency_pause,
+    __client_accounts_withdraw_fees,
+    __client_accounts_initialize_config,
+    __client_accounts_update_config,
+    __client_accounts_propose_admin,
+    __client_accounts_accept_admin,
+    __client_accounts_renounce_admin,
+    __client_accounts_settle_dispute,
+    __client_accounts_update_committee_quorum,
 };
 
 #[program]
@@ -33,6 +79,8 @@ pub mod prediction_market {
         question: String,
         options: Vec<String>,
         end_time: i64,
+        resolution: ResolutionParams,
+        pricing: PricingMode,
     ) -> Result<()> {
         instructions::create_market::handler(
             ctx,
@@ -40,6 +88,8 @@ pub mod prediction_market {
             question,
             options,
             end_time,
+            resolution,
+            pricing,
         )
     }
 
@@ -51,10 +101,139 @@ pub mod prediction_market {
         instructions::place_bet::handler(ctx, outcome_index, amount)
     }
 
+    pub fn withdraw_bet(
+        ctx: Context<WithdrawBet>,
+        option_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_bet::handler(ctx, option_index, amount)
+    }
+
+    pub fn buy_shares(
+        ctx: Context<BuyShares>,
+        option_index: u8,
+        shares: u64,
+        max_cost: u64,
+    ) -> Result<()> {
+        instructions::buy_shares::handler(ctx, option_index, shares, max_cost)
+    }
+
+    pub fn sell_shares(
+        ctx: Context<SellShares>,
+        option_index: u8,
+        shares: u64,
+        min_refund: u64,
+    ) -> Result<()> {
+        instructions::sell_shares::handler(ctx, option_index, shares, min_refund)
+    }
+
+    pub fn create_lp_mint(
+        ctx: Context<CreateLpMint>,
+    ) -> Result<()> {
+        instructions::create_lp_mint::handler(ctx)
+    }
+
+    pub fn add_liquidity(
+        ctx: Context<AddLiquidity>,
+        amount: u64,
+        min_lp_tokens: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity::handler(ctx, amount, min_lp_tokens)
+    }
+
+    pub fn withdraw_liquidity(
+        ctx: Context<WithdrawLiquidity>,
+        lp_tokens: u64,
+    ) -> Result<()> {
+        instructions::withdraw_liquidity::handler(ctx, lp_tokens)
+    }
+
     pub fn resolve_market(
         ctx: Context<ResolveMarket>,
+        winning_option: Option<u8>,
+    ) -> Result<()> {
+        instructions::resolve_market::handler(ctx, winning_option)
+    }
+
+    pub fn resolve_with_oracle(
+        ctx: Context<ResolveWithOracle>,
+    ) -> Result<()> {
+        instructions::resolve_with_oracle::handler(ctx)
+    }
+
+    pub fn propose_outcome(
+        ctx: Context<ProposeOutcome>,
+        option_index: u8,
+    ) -> Result<()> {
+        instructions::propose_outcome::handler(ctx, option_index)
+    }
+
+    pub fn dispute_outcome(
+        ctx: Context<DisputeOutcome>,
+    ) -> Result<()> {
+        instructions::dispute_outcome::handler(ctx)
+    }
+
+    pub fn finalize_resolution(
+        ctx: Context<FinalizeResolution>,
+    ) -> Result<()> {
+        instructions::finalize_resolution::handler(ctx)
+    }
+
+    pub fn expire_dispute(
+        ctx: Context<ExpireDispute>,
+    ) -> Result<()> {
+        instructions::expire_dispute::handler(ctx)
+    }
+
+    pub fn commit_vote(
+        ctx: Context<CommitVote>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_vote::handler(ctx, commitment)
+    }
+
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        option_index: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_vote::handler(ctx, option_index, salt)
+    }
+
+    pub fn finalize_committee_vote(
+        ctx: Context<FinalizeCommitteeVote>,
+    ) -> Result<()> {
+        instructions::finalize_committee_vote::handler(ctx)
+    }
+
+    pub fn create_share_mint(
+        ctx: Context<CreateShareMint>,
+        option_index: u8,
+    ) -> Result<()> {
+        instructions::create_share_mint::handler(ctx, option_index)
+    }
+
+    pub fn mint_complete_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintCompleteSet<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::mint_complete_set::handler(ctx, amount)
+    }
+
+    pub fn redeem_complete_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemCompleteSet<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::redeem_complete_set::handler(ctx, amount)
+    }
+
+    pub fn redeem_shares(
+        ctx: Context<RedeemShares>,
+        option_index: u8,
+        amount: u64,
     ) -> Result<()> {
-        instructions::resolve_market::handler(ctx)
+        instructions::redeem_shares::handler(ctx, option_index, amount)
     }
 
     pub fn claim_winnings(
@@ -63,11 +242,83 @@ pub mod prediction_market {
         instructions::claim_winnings::handler(ctx)
     }
 
+    pub fn cancel_market(
+        ctx: Context<CancelMarket>,
+    ) -> Result<()> {
+        instructions::cancel_market::handler(ctx)
+    }
+
+    pub fn refund_bet(
+        ctx: Context<RefundBet>,
+    ) -> Result<()> {
+        instructions::refund_bet::handler(ctx)
+    }
+
+    pub fn close_market(
+        ctx: Context<CloseMarket>,
+    ) -> Result<()> {
+        instructions::close_market::handler(ctx)
+    }
+
     pub fn emergency_pause(
         ctx: Context<EmergencyPause>,
         paused: bool,
     ) -> Result<()> {
         instructions::admin::emergency_pause::handler(ctx, paused)
     }
+
+    pub fn withdraw_fees(
+        ctx: Context<WithdrawFees>,
+    ) -> Result<()> {
+        instructions::admin::withdraw_fees::handler(ctx)
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        admin: Pubkey,
+        params: ConfigParams,
+    ) -> Result<()> {
+        instructions::admin::initialize_config::handler(ctx, admin, params)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        params: ConfigParams,
+    ) -> Result<()> {
+        instructions::admin::update_config::handler(ctx, params)
+    }
+
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::propose_admin::handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(
+        ctx: Context<AcceptAdmin>,
+    ) -> Result<()> {
+        instructions::admin::accept_admin::handler(ctx)
+    }
+
+    pub fn renounce_admin(
+        ctx: Context<RenounceAdmin>,
+    ) -> Result<()> {
+        instructions::admin::renounce_admin::handler(ctx)
+    }
+
+    pub fn settle_dispute(
+        ctx: Context<SettleDispute>,
+        winning_option: u8,
+    ) -> Result<()> {
+        instructions::admin::settle_dispute::handler(ctx, winning_option)
+    }
+
+    pub fn update_committee_quorum(
+        ctx: Context<UpdateCommitteeQuorum>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::admin::update_committee_quorum::handler(ctx, threshold)
+    }
 }
 
@@ -0,0 +1,331 @@
+// programs/prediction_market/src/lmsr.rs
+
+// Logarithmic Market Scoring Rule with liquidity `b`:
+// `C(q) = b * ln(sum_i exp(q_i / b))`, a trade costs `C(q') - C(q)`.
+// Shares and collateral share the same base units, a winning share pays one
+// unit, so the market maker can lose at most `C(0) = b * ln(n)`.
+//
+// Integer fixed point with `SCALE` (1e18) in u128, no floats, so it runs
+// the same on BPF. `C` is evaluated as `max(q) + b * ln(sum exp((q_i - max) / b))`
+// to keep every exponent non-positive.
+
+use anchor_lang::prelude::*;
+use crate::errors::*;
+
+pub const SCALE: u128 = 1_000_000_000_000_000_000;
+
+const LN_2: u128 = 693_147_180_559_945_309;
+
+// exp(-x) is below 1e-18 past this point
+const EXP_CUTOFF: u128 = 42 * SCALE;
+
+/// `exp(-x)` for `x >= 0`, both scaled by `SCALE`.
+pub fn exp_neg(x: u128) -> u128 {
+    if x >= EXP_CUTOFF {
+        return 0;
+    }
+    
+    // exp(-x) = 2^-k * exp(-r), 0 <= r < ln 2
+    let k = x / LN_2;
+    let r = x - k * LN_2;
+    
+    // Alternating Taylor series, terms shrink below 1e-18 within ~20 steps
+    let mut term = SCALE;
+    let mut positive = SCALE;
+    let mut negative = 0u128;
+    let mut i = 1u128;
+    while term > 0 {
+        term = term * r / SCALE / i;
+        if i % 2 == 1 {
+            negative += term;
+        } else {
+            positive += term;
+        }
+        i += 1;
+    }
+    
+    (positive - negative) >> k
+}
+
+/// `ln(x)` for `x >= 1`, both scaled by `SCALE`.
+pub fn ln(x: u128) -> Result<u128> {
+    require!(x >= SCALE, PredictionMarketError::MathOverflow);
+    
+    // x = 2^k * m, 1 <= m < 2
+    let k = (x / SCALE).ilog2();
+    let m = x >> k;
+    
+    // ln(m) = 2 * atanh(z) = 2 * sum z^(2j+1) / (2j+1), z = (m - 1) / (m + 1) < 1/3
+    let z = (m - SCALE) * SCALE / (m + SCALE);
+    let z_squared = z * z / SCALE;
+    let mut power = z;
+    let mut sum = 0u128;
+    let mut j = 1u128;
+    while power > 0 {
+        sum += power / j;
+        power = power * z_squared / SCALE;
+        j += 2;
+    }
+    
+    Ok(k as u128 * LN_2 + 2 * sum)
+}
+
+/// `C(q)` scaled by `SCALE`.
+pub fn cost(quantities: &[u64], liquidity: u64) -> Result<u128> {
+    require!(liquidity > 0, PredictionMarketError::InvalidLiquidity);
+    
+    let max = quantities.iter().copied().max().unwrap_or(0);
+    let sum = quantities
+        .iter()
+        .map(|q| exp_neg((max - q) as u128 * SCALE / liquidity as u128))
+        .sum::<u128>();
+    
+    (liquidity as u128)
+        .checked_mul(ln(sum)?)
+        .and_then(|log_term| log_term.checked_add(max as u128 * SCALE))
+        .ok_or(error!(PredictionMarketError::MathOverflow))
+}
+
+/// Collateral to buy `shares` of `option`, rounded up in the market's favor.
+pub fn buy_cost(quantities: &[u64], liquidity: u64, option: usize, shares: u64) -> Result<u64> {
+    let mut after = quantities.to_vec();
+    after[option] = after[option]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    let difference = cost(&after, liquidity)?
+        .checked_sub(cost(quantities, liquidity)?)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    u64::try_from(difference.div_ceil(SCALE))
+        .map_err(|_| error!(PredictionMarketError::MathOverflow))
+}
+
+/// Collateral paid for selling `positions` (shares per option) back,
+/// rounded down in the market's favor.
+pub fn sell_refund(quantities: &[u64], liquidity: u64, positions: &[u64]) -> Result<u64> {
+    let after = quantities
+        .iter()
+        .zip(positions)
+        .map(|(q, position)| q.checked_sub(*position))
+        .collect::<Option<Vec<u64>>>()
+        .ok_or(PredictionMarketError::InsufficientPosition)?;
+    
+    let difference = cost(quantities, liquidity)?
+        .checked_sub(cost(&after, liquidity)?)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    u64::try_from(difference / SCALE)
+        .map_err(|_| error!(PredictionMarketError::MathOverflow))
+}
+
+/// Creator funding that covers the worst-case loss `b * ln(n)`, plus one
+/// unit for the fixed-point error.
+pub fn subsidy(liquidity: u64, options: usize) -> Result<u64> {
+    let worst_case = cost(&vec![0; options], liquidity)?;
+    u64::try_from(worst_case.div_ceil(SCALE) + 1)
+        .map_err(|_| error!(PredictionMarketError::MathOverflow))
+}
+
+/// Instantaneous prices `exp(q_i / b) / sum exp(q_j / b)` in basis points.
+pub fn prices_bps(quantities: &[u64], liquidity: u64) -> Vec<u64> {
+    let max = quantities.iter().copied().max().unwrap_or(0);
+    let weights: Vec<u128> = quantities
+        .iter()
+        .map(|q| exp_neg((max - q) as u128 * SCALE / liquidity.max(1) as u128))
+        .collect();
+    let total: u128 = weights.iter().sum();
+    
+    weights
+        .iter()
+        .map(|weight| (weight * 10_000 / total) as u64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+    
+    // Reference values carry 40 digits, 22 more than `SCALE`, and the
+    // series are summed exactly in big integers
+    const REFERENCE_DIGITS: u32 = 40;
+    
+    fn reference_scale() -> BigUint {
+        BigUint::from(10u32).pow(REFERENCE_DIGITS)
+    }
+    
+    // Truncates a reference value to `SCALE`
+    fn to_scale(x: &BigUint) -> u128 {
+        let shift = BigUint::from(10u32).pow(REFERENCE_DIGITS - 18);
+        u128::try_from(x / shift).unwrap()
+    }
+    
+    /// `exp(numerator / denominator)` scaled by the reference scale.
+    fn reference_exp(numerator: u128, denominator: u128) -> BigUint {
+        let mut term = reference_scale();
+        let mut sum = term.clone();
+        let mut i = 1u128;
+        while term > BigUint::ZERO {
+            term = term * numerator / (denominator * i);
+            sum += &term;
+            i += 1;
+        }
+        sum
+    }
+    
+    /// `exp(-numerator / denominator)` scaled by the reference scale.
+    fn reference_exp_neg(numerator: u128, denominator: u128) -> BigUint {
+        reference_scale().pow(2) / reference_exp(numerator, denominator)
+    }
+    
+    /// `ln(x)` for `x >= 1`, both scaled by the reference scale.
+    fn reference_ln(x: &BigUint) -> BigUint {
+        let one = reference_scale();
+        let z = (x - &one) * &one / (x + &one);
+        let z_squared = &z * &z / &one;
+        let mut power = z;
+        let mut sum = BigUint::ZERO;
+        let mut j = 1u32;
+        while power > BigUint::ZERO {
+            sum += &power / j;
+            power = power * &z_squared / &one;
+            j += 2;
+        }
+        sum * 2u32
+    }
+    
+    /// `C(q)` scaled by the reference scale.
+    fn reference_cost(quantities: &[u64], liquidity: u64) -> BigUint {
+        let max = *quantities.iter().max().unwrap();
+        let sum = quantities
+            .iter()
+            .map(|q| reference_exp_neg((max - q) as u128, liquidity as u128))
+            .sum::<BigUint>();
+        reference_scale() * max + reference_ln(&sum) * liquidity
+    }
+    
+    #[test]
+    fn exp_and_ln_match_reference() {
+        // Within a few units of 1e-18
+        for i in 0..400u128 {
+            let x = i * SCALE / 10;
+            let expected = to_scale(&reference_exp_neg(x, SCALE));
+            assert!(exp_neg(x).abs_diff(expected) <= 2, "exp(-{x})");
+        }
+        
+        for i in 0..400u128 {
+            let x = SCALE + i * SCALE / 40;
+            let expected = to_scale(&reference_ln(&(reference_scale() * x / SCALE)));
+            assert!(ln(x).unwrap().abs_diff(expected) <= 32, "ln({x})");
+        }
+        assert_eq!(ln(SCALE).unwrap(), 0);
+        assert!(ln(SCALE - 1).is_err());
+    }
+    
+    #[test]
+    fn cost_matches_reference() {
+        let cases: [(&[u64], u64); 5] = [
+            (&[0, 0], 1_000_000),
+            (&[5_000_000, 0], 1_000_000),
+            (&[1_000, 2_000, 3_000], 10_000),
+            (&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0], 1_000_000_000),
+            (&[90_000_000_000, 1, 45_000_000_000], 10_000_000_000),
+        ];
+        // Truncation only ever rounds down, by at most 32 units of 1e-18 per unit of `b`
+        for (quantities, liquidity) in cases {
+            let expected = to_scale(&reference_cost(quantities, liquidity));
+            let actual = cost(quantities, liquidity).unwrap();
+            assert!(actual <= expected + 1, "{quantities:?}");
+            assert!(expected - actual <= 32 * liquidity as u128, "{quantities:?}");
+        }
+    }
+    
+    #[test]
+    fn worst_case_loss_is_funded() {
+        for options in 2..=10 {
+            let liquidity = 1_000_000_000;
+            let worst_case = reference_cost(&vec![0; options], liquidity);
+            let funded = subsidy(liquidity, options).unwrap();
+            assert!(reference_scale() * funded >= worst_case);
+            assert!(reference_scale() * (funded - 2) < worst_case);
+        }
+    }
+    
+    #[test]
+    fn cost_bounds_hold() {
+        // max(q) <= C(q) <= max(q) + b ln n
+        let liquidity = 50_000;
+        let quantities = [10_000, 250_000, 0, 120_000];
+        let c = cost(&quantities, liquidity).unwrap();
+        let ln_4 = to_scale(&reference_ln(&(reference_scale() * 4u32)));
+        assert!(c >= 250_000 * SCALE);
+        assert!(c <= 250_000 * SCALE + liquidity as u128 * ln_4);
+    }
+    
+    #[test]
+    fn prices_sum_to_one() {
+        let quantities = [3_000_000, 1_000_000, 0];
+        let prices = prices_bps(&quantities, 1_000_000);
+        let total: u64 = prices.iter().sum();
+        assert!((9_997..=10_000).contains(&total));
+        assert!(prices[0] > prices[1] && prices[1] > prices[2]);
+        assert_eq!(prices_bps(&[0, 0], 1_000), vec![5_000, 5_000]);
+    }
+    
+    #[test]
+    fn round_trip_never_profits_the_trader() {
+        let liquidity = 1_000_000;
+        let mut quantities = vec![400_000, 0, 2_000_000];
+        for shares in [1, 999, 250_000, 3_000_000] {
+            for option in 0..quantities.len() {
+                let paid = buy_cost(&quantities, liquidity, option, shares).unwrap();
+                quantities[option] += shares;
+                let mut position = vec![0; quantities.len()];
+                position[option] = shares;
+                let refunded = sell_refund(&quantities, liquidity, &position).unwrap();
+                quantities[option] -= shares;
+                assert!(refunded <= paid);
+                assert!(paid - refunded <= 2);
+            }
+        }
+    }
+    
+    #[test]
+    fn trades_are_path_independent() {
+        let liquidity = 2_000_000;
+        let start = [0u64, 0, 0];
+        
+        let first = buy_cost(&start, liquidity, 0, 700_000).unwrap()
+            + buy_cost(&[700_000, 0, 0], liquidity, 1, 1_300_000).unwrap();
+        let second = buy_cost(&start, liquidity, 1, 1_300_000).unwrap()
+            + buy_cost(&[0, 1_300_000, 0], liquidity, 0, 700_000).unwrap();
+        assert!(first.abs_diff(second) <= 1);
+        
+        // Price of a marginal share matches the reference price
+        let difference = reference_cost(&[700_001, 1_300_000, 0], liquidity)
+            - reference_cost(&[700_000, 1_300_000, 0], liquidity);
+        let expected = (difference + reference_scale() - 1u32) / reference_scale();
+        let actual = buy_cost(&[700_000, 1_300_000, 0], liquidity, 0, 1).unwrap();
+        assert_eq!(BigUint::from(actual), expected);
+    }
+    
+    #[test]
+    fn market_stays_solvent_for_any_winner() {
+        // Subsidy plus everything paid covers the winning shares
+        let liquidity = 10_000_000;
+        let mut quantities = vec![0u64; 3];
+        let mut held = subsidy(liquidity, 3).unwrap();
+        for (option, shares) in [(0, 5_000_000), (1, 80_000_000), (0, 1), (2, 33_333_333), (1, 7)] {
+            held += buy_cost(&quantities, liquidity, option, shares).unwrap();
+            quantities[option] += shares;
+        }
+        
+        let mut position = vec![0; 3];
+        position[1] = 40_000_000;
+        held -= sell_refund(&quantities, liquidity, &position).unwrap();
+        quantities[1] -= 40_000_000;
+        
+        for q in &quantities {
+            assert!(held >= *q);
+        }
+    }
+}
@@ -0,0 +1,263 @@
+// programs/prediction_market/oracle.rs
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Price read from an oracle account, in the feed's own fixed-point units.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Decodes a price account layout. New oracle providers plug in by adding an
+/// `OracleSource` variant and an adapter for it.
+pub trait PriceFeedAdapter {
+    /// Reads the price of a feed account owned by `oracle_program`.
+    fn read_price(info: &AccountInfo, oracle_program: &Pubkey) -> Result<OraclePrice>;
+}
+
+/// Pyth v2 (legacy push oracle) price account.
+pub struct PythLegacyAdapter;
+
+impl PythLegacyAdapter {
+    pub const MAGIC: u32 = 0xa1b2_c3d4;
+    pub const VERSION: u32 = 2;
+    pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+    pub const STATUS_TRADING: u32 = 1;
+    
+    // Field offsets in the price account
+    const MAGIC_OFFSET: usize = 0;
+    const VERSION_OFFSET: usize = 4;
+    const ACCOUNT_TYPE_OFFSET: usize = 8;
+    const EXPO_OFFSET: usize = 20;
+    const TIMESTAMP_OFFSET: usize = 96;
+    const AGG_PRICE_OFFSET: usize = 208;
+    const AGG_CONF_OFFSET: usize = 216;
+    const AGG_STATUS_OFFSET: usize = 224;
+    pub const MIN_LEN: usize = 240;
+}
+
+impl PythLegacyAdapter {
+    /// Decodes the price account data; the caller checks the account owner.
+    pub fn decode(data: &[u8]) -> Result<OraclePrice> {
+        require!(
+            data.len() >= Self::MIN_LEN,
+            PredictionMarketError::InvalidOracleAccount
+        );
+        require!(
+            read_u32(data, Self::MAGIC_OFFSET) == Self::MAGIC
+                && read_u32(data, Self::VERSION_OFFSET) == Self::VERSION
+                && read_u32(data, Self::ACCOUNT_TYPE_OFFSET) == Self::ACCOUNT_TYPE_PRICE,
+            PredictionMarketError::InvalidOracleAccount
+        );
+        require!(
+            read_u32(data, Self::AGG_STATUS_OFFSET) == Self::STATUS_TRADING,
+            PredictionMarketError::OraclePriceUnavailable
+        );
+        
+        Ok(OraclePrice {
+            price: read_u64(data, Self::AGG_PRICE_OFFSET) as i64,
+            conf: read_u64(data, Self::AGG_CONF_OFFSET),
+            expo: read_u32(data, Self::EXPO_OFFSET) as i32,
+            publish_time: read_u64(data, Self::TIMESTAMP_OFFSET) as i64,
+        })
+    }
+}
+
+impl PriceFeedAdapter for PythLegacyAdapter {
+    fn read_price(info: &AccountInfo, oracle_program: &Pubkey) -> Result<OraclePrice> {
+        // Anyone can write Pyth's layout into an account they own
+        require_keys_eq!(
+            *info.owner,
+            *oracle_program,
+            PredictionMarketError::InvalidOracleAccount
+        );
+        
+        let data = info.try_borrow_data()?;
+        Self::decode(&data)
+    }
+}
+
+pub fn load_price(
+    source: &OracleSource,
+    info: &AccountInfo,
+    oracle_program: &Pubkey,
+) -> Result<OraclePrice> {
+    match source {
+        OracleSource::PythLegacy => PythLegacyAdapter::read_price(info, oracle_program),
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+pub(crate) fn mock_pyth_account(price: i64, expo: i32, publish_time: i64, status: u32) -> Vec<u8> {
+    let mut data = vec![0u8; 3312]; // Full size of a Pyth v2 price account
+    data[0..4].copy_from_slice(&PythLegacyAdapter::MAGIC.to_le_bytes());
+    data[4..8].copy_from_slice(&PythLegacyAdapter::VERSION.to_le_bytes());
+    data[8..12].copy_from_slice(&PythLegacyAdapter::ACCOUNT_TYPE_PRICE.to_le_bytes());
+    data[20..24].copy_from_slice(&expo.to_le_bytes());
+    data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+    data[208..216].copy_from_slice(&price.to_le_bytes());
+    data[216..224].copy_from_slice(&1_000u64.to_le_bytes());
+    data[224..228].copy_from_slice(&status.to_le_bytes());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn reads_mock_price_account() {
+        let data = mock_pyth_account(10_050_000_000_000, -8, 1_700_000_000, PythLegacyAdapter::STATUS_TRADING);
+        let price = PythLegacyAdapter::decode(&data).unwrap();
+        
+        assert_eq!(price, OraclePrice {
+            price: 10_050_000_000_000,
+            conf: 1_000,
+            expo: -8,
+            publish_time: 1_700_000_000,
+        });
+    }
+    
+    #[test]
+    fn reads_negative_prices() {
+        let data = mock_pyth_account(-42, 0, 1, PythLegacyAdapter::STATUS_TRADING);
+        assert_eq!(PythLegacyAdapter::decode(&data).unwrap().price, -42);
+    }
+    
+    #[test]
+    fn rejects_foreign_accounts() {
+        let mut data = mock_pyth_account(1, -8, 1, PythLegacyAdapter::STATUS_TRADING);
+        data[0] = 0;
+        assert_eq!(
+            PythLegacyAdapter::decode(&data).unwrap_err(),
+            PredictionMarketError::InvalidOracleAccount.into()
+        );
+        assert_eq!(
+            PythLegacyAdapter::decode(&[0u8; 64]).unwrap_err(),
+            PredictionMarketError::InvalidOracleAccount.into()
+        );
+    }
+    
+    #[test]
+    fn rejects_feeds_not_owned_by_the_oracle_program() {
+        let (feed, pyth, impostor) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut lamports = 0u64;
+        let mut data = mock_pyth_account(1, -8, 1, PythLegacyAdapter::STATUS_TRADING);
+        let info = AccountInfo::new(&feed, false, false, &mut lamports, &mut data, &impostor, false, 0);
+        
+        assert_eq!(
+            load_price(&OracleSource::PythLegacy, &info, &pyth).unwrap_err(),
+            PredictionMarketError::InvalidOracleAccount.into()
+        );
+        assert!(load_price(&OracleSource::PythLegacy, &info, &impostor).is_ok());
+    }
+    
+    #[test]
+    fn rejects_halted_feeds() {
+        let data = mock_pyth_account(1, -8, 1, 0);
+        assert_eq!(
+            PythLegacyAdapter::decode(&data).unwrap_err(),
+            PredictionMarketError::OraclePriceUnavailable.into()
+        );
+    }
+    
+    #[test]
+    fn threshold_decides_binary_outcome() {
+        const HUNDRED_K: i64 = 100_000 * 100_000_000; // $100k at expo -8
+        let oracle = OracleConfig {
+            feed: Pubkey::new_unique(),
+            source: OracleSource::PythLegacy,
+            comparator: PriceComparator::Above,
+            threshold: HUNDRED_K,
+            expo: -8,
+            max_staleness: 60,
+        };
+        let price = |value| OraclePrice { price: value, conf: 0, expo: -8, publish_time: 0 };
+        
+        assert_eq!(oracle.winning_option(&price(HUNDRED_K + 1)).unwrap(), 0);
+        assert_eq!(oracle.winning_option(&price(HUNDRED_K)).unwrap(), 1);
+        
+        let below = OracleConfig { comparator: PriceComparator::AtOrBelow, ..oracle.clone() };
+        assert_eq!(below.winning_option(&price(HUNDRED_K)).unwrap(), 0);
+        
+        let mismatched = OraclePrice { expo: -6, ..price(1) };
+        assert!(oracle.winning_option(&mismatched).is_err());
+    }
+    
+    #[test]
+    fn uncertain_prices_do_not_decide() {
+        let oracle = OracleConfig {
+            feed: Pubkey::new_unique(),
+            source: OracleSource::PythLegacy,
+            comparator: PriceComparator::Above,
+            threshold: 1_000_000,
+            expo: -8,
+            max_staleness: 60,
+        };
+        let price = |value, conf| OraclePrice { price: value, conf, expo: -8, publish_time: 0 };
+        
+        assert_eq!(oracle.winning_option(&price(1_000_500, 499)).unwrap(), 0);
+        assert_eq!(oracle.winning_option(&price(999_500, 500)).unwrap(), 1);
+        for uncertain in [price(1_000_500, 500), price(999_500, 501), price(i64::MAX, u64::MAX)] {
+            assert_eq!(
+                oracle.winning_option(&uncertain).unwrap_err(),
+                PredictionMarketError::OraclePriceUncertain.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn resolves_from_mock_price_account() {
+        let end_time = 1_700_000_000;
+        let oracle = OracleConfig {
+            feed: Pubkey::new_unique(),
+            source: OracleSource::PythLegacy,
+            comparator: PriceComparator::AtOrAbove,
+            threshold: 100_000 * 100_000_000,
+            expo: -8,
+            max_staleness: 60,
+        };
+        
+        let pyth = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = mock_pyth_account(
+            101_000 * 100_000_000,
+            -8,
+            end_time + 5,
+            PythLegacyAdapter::STATUS_TRADING,
+        );
+        let info = AccountInfo::new(&oracle.feed, false, false, &mut lamports, &mut data, &pyth, false, 0);
+        
+        let price = load_price(&oracle.source, &info, &pyth).unwrap();
+        assert_eq!(oracle.resolve_outcome(&price, end_time, end_time + 30).unwrap(), 0);
+        
+        // Published before the close, long after it, or too old by the time
+        // anyone resolves
+        assert_eq!(
+            oracle.resolve_outcome(&price, end_time + 10, end_time + 30).unwrap_err(),
+            PredictionMarketError::StaleOraclePrice.into()
+        );
+        assert_eq!(
+            oracle.resolve_outcome(&price, end_time - 56, end_time + 30).unwrap_err(),
+            PredictionMarketError::StaleOraclePrice.into()
+        );
+        assert!(oracle.resolve_outcome(&price, end_time - 55, end_time + 30).is_ok());
+        assert_eq!(
+            oracle.resolve_outcome(&price, end_time, end_time + 66).unwrap_err(),
+            PredictionMarketError::StaleOraclePrice.into()
+        );
+    }
+}
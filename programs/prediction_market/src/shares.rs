@@ -0,0 +1,102 @@
+// programs/prediction_market/src/shares.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, mint_to, Burn, MintTo, Token};
+use crate::state::*;
+use crate::errors::*;
+
+/// Share mint of `option_index`. Every option of a market has one, minted
+/// only by the market PDA against deposited collateral.
+pub fn share_mint_address(market: &Pubkey, option_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[OutcomeShares::SEED_PREFIX, market.as_ref(), &[option_index]],
+        &crate::ID,
+    )
+    .0
+}
+
+/// One share of every option of a market, passed as `remaining_accounts` in
+/// option order: `[mint_0, holder_account_0, mint_1, holder_account_1, ...]`.
+/// The token program checks the holder accounts against the mints.
+pub struct OutcomeShares<'a, 'info> {
+    pub legs: Vec<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)>,
+}
+
+impl<'a, 'info> OutcomeShares<'a, 'info> {
+    pub const SEED_PREFIX: &'static [u8] = b"shares";
+    
+    pub fn load(market: &Account<'info, Market>, accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        require!(
+            accounts.len() == market.options.len() * 2,
+            PredictionMarketError::InvalidShareAccounts
+        );
+        
+        let legs = accounts
+            .chunks(2)
+            .enumerate()
+            .map(|(option_index, leg)| {
+                require_keys_eq!(
+                    leg[0].key(),
+                    share_mint_address(&market.key(), option_index as u8),
+                    PredictionMarketError::InvalidShareAccounts
+                );
+                Ok((&leg[0], &leg[1]))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        Ok(Self { legs })
+    }
+    
+    /// Mints `amount` shares of every option to the holder accounts.
+    pub fn mint(
+        &self,
+        market: &Account<'info, Market>,
+        token_program: &Program<'info, Token>,
+        amount: u64,
+    ) -> Result<()> {
+        let market_id = market.market_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"market",
+            market.creator.as_ref(),
+            market_id.as_ref(),
+            &[market.bump],
+        ]];
+        
+        for (mint, holder) in &self.legs {
+            let cpi_context = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                MintTo {
+                    mint: (*mint).clone(),
+                    to: (*holder).clone(),
+                    authority: market.to_account_info(),
+                },
+                signer_seeds,
+            );
+            mint_to(cpi_context, amount)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Burns `amount` shares of every option from the holder accounts.
+    pub fn burn(
+        &self,
+        owner: &AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        amount: u64,
+    ) -> Result<()> {
+        for (mint, holder) in &self.legs {
+            let cpi_context = CpiContext::new(
+                token_program.to_account_info(),
+                Burn {
+                    mint: (*mint).clone(),
+                    from: (*holder).clone(),
+                    authority: owner.clone(),
+                },
+            );
+            burn(cpi_context, amount)?;
+        }
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+// programs/prediction_market/state/fee_vault.rs
+
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    pub market: Pubkey,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl FeeVault {
+    pub const SEED_PREFIX: &'static [u8] = b"fee_vault";
+}
@@ -1,6 +1,10 @@
 // programs/prediction_market/state/market.rs
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::errors::*;
+use crate::{cpmm, lmsr};
+use crate::oracle::OraclePrice;
+use crate::state::PlatformConfig;
 
 #[account]
 #[derive(InitSpace)]
@@ -8,6 +12,10 @@ pub struct Market {
     // Kimlik
     pub creator: Pubkey,
     pub market_id: u64,
+    pub resolver: Pubkey, // Sonucu belirleyen hesap (varsayılan: creator)
+    pub collateral: Option<Collateral>, // None: native SOL
+    pub pricing: PricingMode,
+    pub subsidy: u64, // Yaratıcının yatırdığı likidite fonu
     
     // Market detayları
     #[max_len(MAX_QUESTION_LEN)]
@@ -23,16 +31,40 @@ pub struct Market {
     // Bahis havuzları
     #[max_len(MAX_OPTIONS)]
     pub option_pools: Vec<u64>, // Her seçenek için toplam bahis
+    #[max_len(MAX_OPTIONS)]
+    pub pool_updated_at: Vec<i64>, // Her seçenek havuzunun son değiştiği zaman
+    #[max_len(MAX_OPTIONS)]
+    pub option_bettors: Vec<u32>,  // Her seçenekte pozisyonu olan farklı kullanıcı sayısı
     pub total_pool: u64,
     pub total_fees: u64,
+    pub outstanding_claims: u64, // Henüz talep edilmemiş kazanan bahisler
+    pub share_collateral: u64,   // Tam setlerin teminatı, havuzdan ayrı
+    #[max_len(MAX_OPTIONS)]
+    pub share_supply: Vec<u64>,  // Seçenek başına dolaşımdaki pay tokenı
+    pub amm: Option<AmmPool>,    // Sadece Cpmm modunda
     
-    // Liderlik takibi (V1 için basit)
+    // Liderlik takibi
     pub leading_option: Option<u8>,
     pub leading_since: Option<i64>,
+    #[max_len(MAX_OPTIONS)]
+    pub leadership_seconds: Vec<i64>, // Seçenek başına tamamlanmış liderlik süresi
+    
+    // Çözüm yöntemi
+    pub resolution_mode: ResolutionMode,
+    pub tie_policy: TiePolicy,        // Algoritmik çözümde beraberlik
+    pub scoring_weights: ScoringWeights, // Algoritmik skor ağırlıkları
+    pub oracle: Option<OracleConfig>, // Sadece Oracle modunda
+    #[max_len(MAX_COMMITTEE_MEMBERS)]
+    pub committee: Vec<Pubkey>,       // Sadece Committee modunda
+    pub committee_threshold: u8,      // Sonuç için gereken oy sayısı (M)
+    pub commit_deadline: i64,         // Gizli oyların son zamanı
+    pub reveal_deadline: i64,         // Oyların açıklanmasının son zamanı
     
     // Durum
     pub phase: MarketPhase,
     pub winner: Option<u8>,
+    #[max_len(MAX_OPTIONS)]
+    pub winning_options: Vec<u8>, // Beraberlikte pay bölüşen tüm seçenekler
     pub paused: bool,
     
     // PDA bump
@@ -47,25 +79,835 @@ pub enum MarketPhase {
     Cancelled,  // 
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub enum ResolutionMode {
+    Algorithmic,    // Zaman ağırlıklı skor (calculate_winner)
+    ManualResolver, // Resolver sonucu bildirir
+    Oracle,         // Fiyat feed'i belirler
+    Committee,      // Komite oylaması
+    Optimistic,     // Teminatlı öneri + itiraz süresi
+}
+
+/// How bets are priced. Parimutuel splits the pool among winners when the
+/// market resolves; LMSR and CPMM sell shares at `lmsr::cost` or
+/// constant-product prices, paying one unit per winning share, and
+/// `option_pools` then count the shares traders hold.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub enum PricingMode {
+    Parimutuel,
+    Lmsr { liquidity: u64 }, // b, collateral birimlerinde; en fazla MAX_LMSR_OPTIONS seçenek
+    Cpmm { fee_bps: u16 },   // Sadece iki seçenekli marketler, ücret LP'lere
+}
+
+/// Constant-product pool of a CPMM market, funded by liquidity providers
+/// who hold the market's LP tokens (see `cpmm`).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct AmmPool {
+    pub reserves: [u64; 2], // Havuzdaki Yes/No payları
+    pub lp_supply: u64,
+}
+
+impl AmmPool {
+    pub const SEED_PREFIX: &'static [u8] = b"lp_mint";
+}
+
+/// How `calculate_winner` settles options with equal scores.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub enum TiePolicy {
+    Void,           // Market iptal, herkes iade alır
+    SplitPot,       // Berabere seçenekler havuzu paylaşır
+    EarliestToPool, // Havuzuna en erken ulaşan kazanır
+}
+
+/// Percentages (summing to 100) of the algorithmic score given to time led,
+/// pool share and share of distinct bettors.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, InitSpace)]
+pub struct ScoringWeights {
+    pub time: u8,
+    pub money: u8,
+    pub participants: u8,
+}
+
+impl ScoringWeights {
+    pub fn total(&self) -> u16 {
+        self.time as u16 + self.money as u16 + self.participants as u16
+    }
+    
+    /// Each component lies within the admin-defined bounds.
+    pub fn is_within(&self, min: &ScoringWeights, max: &ScoringWeights) -> bool {
+        (min.time..=max.time).contains(&self.time)
+            && (min.money..=max.money).contains(&self.money)
+            && (min.participants..=max.participants).contains(&self.participants)
+    }
+}
+
+/// Resolution settings chosen at `create_market`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolutionParams {
+    pub mode: ResolutionMode,
+    pub tie_policy: TiePolicy,
+    pub scoring_weights: ScoringWeights,
+    pub resolver: Option<Pubkey>,     // Varsayılan: creator
+    pub oracle: Option<OracleConfig>, // Sadece Oracle modunda
+    pub committee: Vec<Pubkey>,       // Sadece Committee modunda
+    pub committee_threshold: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum VoidReason {
+    NoBets,         // Hiç bahis yok
+    OneSided,       // Sadece tek seçeneğe bahis var
+    NoWinningBets,  // Kazanan seçeneğe bahis yok
+    NoQuorum,       // Komite yeter sayıya ulaşamadı
+    Tie,            // Beraberlik, TiePolicy::Void
+    Unsettled,      // İtiraz süresi içinde çözülmedi
+}
+
+/// Where a committee market is in its commit-reveal schedule.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VotingStage {
+    Betting, // Market henüz bitmedi
+    Commit,  // end_time <= now < commit_deadline
+    Reveal,  // commit_deadline <= now < reveal_deadline
+    Tally,   // reveal_deadline <= now
+}
+
+/// SPL token collateral of a market. Stakes and fees sit in `vault`, a token
+/// account owned by the market PDA; amounts are in the mint's base units.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct Collateral {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub decimals: u8,
+    pub min_bet: u64,      // Mint birimlerinde
+    pub min_velocity: u64, // Mint birimlerinde
+}
+
+/// Binds a two-option market to a price feed: option 0 wins when
+/// `price <comparator> threshold` holds at resolution, option 1 otherwise.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct OracleConfig {
+    pub feed: Pubkey,
+    pub source: OracleSource,
+    pub comparator: PriceComparator,
+    pub threshold: i64,     // Feed'in kendi birimlerinde (price * 10^expo)
+    pub expo: i32,
+    pub max_staleness: i64, // Saniye
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub enum OracleSource {
+    PythLegacy,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub enum PriceComparator {
+    Above,
+    AtOrAbove,
+    Below,
+    AtOrBelow,
+}
+
+impl OracleConfig {
+    /// Outcome for a price that was published after the market closed and is still fresh.
+    pub fn resolve_outcome(&self, price: &OraclePrice, end_time: i64, now: i64) -> Result<u8> {
+        // Published within the staleness window after the close, and still
+        // fresh when resolving
+        require!(
+            price.publish_time >= end_time
+                && price.publish_time <= end_time.saturating_add(self.max_staleness)
+                && now.saturating_sub(price.publish_time) <= self.max_staleness,
+            PredictionMarketError::StaleOraclePrice
+        );
+        
+        self.winning_option(price)
+    }
+    
+    pub fn winning_option(&self, price: &OraclePrice) -> Result<u8> {
+        require!(
+            price.expo == self.expo,
+            PredictionMarketError::OracleExponentMismatch
+        );
+        
+        // Both ends of the confidence interval have to agree on the outcome
+        let low = price.price as i128 - price.conf as i128;
+        let high = price.price as i128 + price.conf as i128;
+        let condition_met = self.condition_met(low);
+        require!(
+            condition_met == self.condition_met(high),
+            PredictionMarketError::OraclePriceUncertain
+        );
+        
+        Ok(if condition_met { 0 } else { 1 })
+    }
+    
+    fn condition_met(&self, price: i128) -> bool {
+        let threshold = self.threshold as i128;
+        match self.comparator {
+            PriceComparator::Above => price > threshold,
+            PriceComparator::AtOrAbove => price >= threshold,
+            PriceComparator::Below => price < threshold,
+            PriceComparator::AtOrBelow => price <= threshold,
+        }
+    }
+}
+
 impl Market {
     pub fn is_active(&self) -> bool {
         self.phase == MarketPhase::Betting && !self.paused
     }
     
+    pub fn cpmm_pool(&mut self) -> Result<&mut AmmPool> {
+        self.amm
+            .as_mut()
+            .ok_or(error!(PredictionMarketError::WrongPricingMode))
+    }
+    
+    /// Liquidity providers still have to withdraw from the pool.
+    pub fn has_open_liquidity(&self) -> bool {
+        self.amm
+            .as_ref()
+            .map(|pool| pool.lp_supply > 0)
+            .unwrap_or(false)
+    }
+    
+    /// Collateral to buy `shares` of an option. Moves the CPMM reserves,
+    /// LMSR prices follow `option_pools`, which the caller updates.
+    pub fn buy_cost(&mut self, option_index: u8, shares: u64) -> Result<u64> {
+        match self.pricing {
+            PricingMode::Lmsr { liquidity } => {
+                lmsr::buy_cost(&self.option_pools, liquidity, option_index as usize, shares)
+            }
+            PricingMode::Cpmm { fee_bps } => {
+                let pool = self.cpmm_pool()?;
+                let swap = cpmm::buy(pool.reserves, option_index as usize, shares, fee_bps)?;
+                pool.reserves = swap.reserves;
+                Ok(swap.collateral)
+            }
+            PricingMode::Parimutuel => err!(PredictionMarketError::WrongPricingMode),
+        }
+    }
+    
+    /// Collateral paid for selling `shares` of an option back, see `buy_cost`.
+    pub fn sell_refund(&mut self, option_index: u8, shares: u64) -> Result<u64> {
+        match self.pricing {
+            PricingMode::Lmsr { liquidity } => {
+                let mut sold = vec![0u64; self.options.len()];
+                sold[option_index as usize] = shares;
+                lmsr::sell_refund(&self.option_pools, liquidity, &sold)
+            }
+            PricingMode::Cpmm { fee_bps } => {
+                let pool = self.cpmm_pool()?;
+                let swap = cpmm::sell(pool.reserves, option_index as usize, shares, fee_bps)?;
+                pool.reserves = swap.reserves;
+                Ok(swap.collateral)
+            }
+            PricingMode::Parimutuel => err!(PredictionMarketError::WrongPricingMode),
+        }
+    }
+    
+    /// Current share prices in basis points, empty for parimutuel markets.
+    pub fn prices_bps(&self) -> Vec<u64> {
+        match (&self.pricing, &self.amm) {
+            (PricingMode::Lmsr { liquidity }, _) => lmsr::prices_bps(&self.option_pools, *liquidity),
+            (PricingMode::Cpmm { .. }, Some(pool)) => cpmm::prices_bps(pool.reserves),
+            _ => Vec::new(),
+        }
+    }
+    
+    pub fn min_bet(&self, config: &PlatformConfig) -> u64 {
+        self.collateral
+            .as_ref()
+            .map(|collateral| collateral.min_bet)
+            .unwrap_or(config.min_bet_amount)
+    }
+    
+    pub fn min_velocity(&self, config: &PlatformConfig) -> u64 {
+        self.collateral
+            .as_ref()
+            .map(|collateral| collateral.min_velocity)
+            .unwrap_or(config.min_velocity)
+    }
+    
+    pub fn can_resolve(&self, authority: &Pubkey, admin: &Pubkey) -> bool {
+        authority == &self.resolver || authority == admin
+    }
+    
+    /// Settles the market on one option, or on several sharing the pot after a tie.
+    pub fn resolve(&mut self, winners: &[u8], now: i64) {
+        self.phase = MarketPhase::Resolved;
+        self.winner = winners.first().copied();
+        self.winning_options = winners.to_vec();
+        self.resolution_time = Some(now);
+        self.outstanding_claims = self.winning_pool();
+    }
+    
+    /// Combined net stake on the winning option(s).
+    pub fn winning_pool(&self) -> u64 {
+        self.winning_options
+            .iter()
+            .map(|option| self.option_pools[*option as usize])
+            .sum()
+    }
+    
+    /// Applies the market's tie policy to the options sharing the best score.
+    /// Returns the winning option(s), or `None` if the market should be voided.
+    pub fn apply_tie_policy(&self, tied: &[u8]) -> Option<Vec<u8>> {
+        if tied.len() <= 1 {
+            return Some(tied.to_vec());
+        }
+        
+        match self.tie_policy {
+            TiePolicy::Void => None,
+            TiePolicy::SplitPot => Some(tied.to_vec()),
+            // Earliest last pool change wins, lowest index if that ties too
+            TiePolicy::EarliestToPool => tied
+                .iter()
+                .min_by_key(|option| (self.pool_updated_at[**option as usize], **option))
+                .map(|option| vec![*option]),
+        }
+    }
+    
+    pub fn record_pool_update(&mut self, option_index: u8, now: i64) {
+        self.pool_updated_at[option_index as usize] = now;
+    }
+    
+    /// Algorithmic score of an option: weighted percentages of the betting
+    /// period it led, of the total pool and of all positions held.
+    pub fn score(&self, option_index: u8, now: i64) -> u128 {
+        let weights = &self.scoring_weights;
+        let i = option_index as usize;
+        
+        let total_duration = (self.end_time - self.start_time).max(1);
+        let time_percentage = (self.leadership_time(option_index, now) as u128 * 100)
+            / total_duration as u128;
+        let time_score = time_percentage * weights.time as u128;
+        
+        let money_score = (self.option_pools[i] as u128 * 100 * weights.money as u128)
+            .checked_div(self.total_pool as u128)
+            .unwrap_or(0);
+        
+        let total_bettors: u128 = self.option_bettors.iter().map(|count| *count as u128).sum();
+        let participant_score = (self.option_bettors[i] as u128 * 100 * weights.participants as u128)
+            .checked_div(total_bettors)
+            .unwrap_or(0);
+        
+        time_score + money_score + participant_score
+    }
+    
+    pub fn committee_index(&self, member: &Pubkey) -> Option<usize> {
+        self.committee.iter().position(|key| key == member)
+    }
+    
+    pub fn voting_stage(&self, now: i64) -> VotingStage {
+        if now < self.end_time {
+            VotingStage::Betting
+        } else if now < self.commit_deadline {
+            VotingStage::Commit
+        } else if now < self.reveal_deadline {
+            VotingStage::Reveal
+        } else {
+            VotingStage::Tally
+        }
+    }
+    
+    pub fn funded_options(&self) -> usize {
+        self.option_pools.iter().filter(|pool| **pool > 0).count()
+    }
+    
+    /// Share of the total pool owed to a winning net stake, rounded down so
+    /// the sum of all payouts never exceeds the pool. With a split pot every
+    /// lamport staked on a tied option earns the same share. LMSR and CPMM
+    /// shares pay one unit each, split the same way.
+    pub fn calculate_payout(&self, stake: u64) -> Result<u64> {
+        require!(
+            self.winner.is_some(),
+            PredictionMarketError::MarketNotResolved
+        );
+        let winning_pool = self.winning_pool();
+        require!(
+            winning_pool > 0 && stake <= winning_pool,
+            PredictionMarketError::NothingToClaim
+        );
+        
+        if self.pricing != PricingMode::Parimutuel {
+            return Ok(stake / self.winning_options.len() as u64);
+        }
+        
+        let payout = (stake as u128)
+            .checked_mul(self.total_pool as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / winning_pool as u128;
+        
+        u64::try_from(payout).map_err(|_| error!(PredictionMarketError::MathOverflow))
+    }
+    
+    /// Resolved markets can be closed once every winner has been paid or the claim window
+    /// has passed, cancelled ones once every stake is refunded or the window has passed.
+    /// Outcome shares and CPMM reserves are backed by the market until redeemed or
+    /// withdrawn, so they always block closing.
+    pub fn is_closable(&self, claim_window: i64, now: i64) -> bool {
+        let window_over = self.resolution_time
+            .map(|resolved_at| now >= resolved_at.saturating_add(claim_window))
+            .unwrap_or(false);
+        
+        let settled = match self.phase {
+            MarketPhase::Resolved => self.outstanding_claims == 0 || window_over,
+            MarketPhase::Cancelled => self.total_pool == 0 || window_over,
+            _ => false,
+        };
+        settled && self.share_collateral == 0 && !self.has_open_liquidity()
+    }
+    
+    /// A cancelled market is fully settled once every stake has been refunded,
+    /// every outcome share redeemed and all liquidity withdrawn. Every open
+    /// position holds a non-zero net stake, so an empty pool means no refunds
+    /// (stake or commission) are left.
+    pub fn is_fully_refunded(&self) -> bool {
+        self.phase == MarketPhase::Cancelled
+            && self.total_pool == 0
+            && self.share_collateral == 0
+            && !self.has_open_liquidity()
+    }
+    
+    pub fn share_decimals(&self) -> u8 {
+        self.collateral
+            .as_ref()
+            .map(|collateral| collateral.decimals)
+            .unwrap_or(NATIVE_DECIMALS)
+    }
+    
+    /// Collateral owed for `amount` AMM-held shares of `option` once the market
+    /// is settled: winning shares split the unit evenly (several only with a
+    /// split pot), shares of a cancelled market are worth 1/n of a complete set.
+    /// Share tokens are paid by `redeem_shares` instead.
+    pub fn share_payout(&self, option: u8, amount: u64) -> u64 {
+        match self.phase {
+            MarketPhase::Resolved if self.winning_options.contains(&option) => {
+                amount / self.winning_options.len() as u64
+            }
+            MarketPhase::Cancelled => amount / self.options.len() as u64,
+            _ => 0,
+        }
+    }
+    
+    /// Burns `amount` share tokens of `option` from the books of a settled
+    /// market and returns the collateral they pay. Redeemable shares (the
+    /// winners', or all of a cancelled market's) split what is left of
+    /// `share_collateral` pro rata, so rounding never strands collateral:
+    /// the last redeemer takes the remainder, and a burn too small to pay
+    /// anything leaves its value to the others.
+    pub fn redeem_shares(&mut self, option: u8, amount: u64) -> Result<u64> {
+        let redeemable = |option: &usize| match self.phase {
+            MarketPhase::Resolved => self.winning_options.contains(&(*option as u8)),
+            MarketPhase::Cancelled => true,
+            _ => false,
+        };
+        require!(
+            redeemable(&(option as usize)),
+            PredictionMarketError::NothingToClaim
+        );
+        
+        let supply: u64 = (0..self.options.len())
+            .filter(redeemable)
+            .map(|option| self.share_supply[option])
+            .sum();
+        require!(
+            amount <= self.share_supply[option as usize],
+            PredictionMarketError::InsufficientPosition
+        );
+        
+        let payout = (self.share_collateral as u128 * amount as u128 / supply as u128) as u64;
+        self.share_supply[option as usize] -= amount;
+        self.share_collateral -= payout;
+        
+        Ok(payout)
+    }
+    
+    /// Percentage (0-100) of the betting period that has elapsed.
+    pub fn elapsed_percentage(&self, now: i64) -> u64 {
+        let elapsed_time = (now - self.start_time).max(0) as u64;
+        let total_duration = (self.end_time - self.start_time).max(1) as u64;
+        (elapsed_time * 100) / total_duration
+    }
+    
+    /// Seconds `option` has led up to `now`, including the current lead.
+    pub fn leadership_time(&self, option: u8, now: i64) -> i64 {
+        let mut seconds = self.leadership_seconds[option as usize];
+        if self.leading_option == Some(option) {
+            let since = self.leading_since.unwrap_or(self.start_time);
+            seconds = seconds.saturating_add(now.saturating_sub(since).max(0));
+        }
+        seconds
+    }
+    
     pub fn update_leader(&mut self, clock: &Clock) {
-        let mut max_pool = 0u64;
-        let mut leader = 0u8;
+        // Equal pools keep the incumbent: the lead only changes on a strictly larger pool
+        let mut leader = self.leading_option
+            .filter(|option| self.option_pools[*option as usize] > 0);
+        let mut max_pool = leader
+            .map(|option| self.option_pools[option as usize])
+            .unwrap_or(0);
         
         for (i, pool) in self.option_pools.iter().enumerate() {
             if *pool > max_pool {
                 max_pool = *pool;
-                leader = i as u8;
+                leader = Some(i as u8);
+            }
+        }
+        
+        if self.leading_option != leader {
+            // Bank the previous leader's time before handing over the lead
+            if let Some(previous) = self.leading_option {
+                self.leadership_seconds[previous as usize] =
+                    self.leadership_time(previous, clock.unix_timestamp);
             }
+            
+            self.leading_option = leader;
+            self.leading_since = leader.map(|_| clock.unix_timestamp);
         }
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn resolved_market(option_pools: Vec<u64>, winner: u8) -> Market {
+    Market {
+        creator: Pubkey::default(),
+        market_id: 0,
+        resolver: Pubkey::default(),
+        collateral: None,
+        pricing: PricingMode::Parimutuel,
+        subsidy: 0,
+        question: String::new(),
+        options: vec![String::new(); option_pools.len()],
+        start_time: 0,
+        end_time: 0,
+        resolution_time: Some(0),
+        total_pool: option_pools.iter().sum(),
+        outstanding_claims: option_pools[winner as usize],
+        share_collateral: 0,
+        share_supply: vec![0; option_pools.len()],
+        amm: None,
+        leadership_seconds: vec![0; option_pools.len()],
+        pool_updated_at: vec![0; option_pools.len()],
+        option_bettors: vec![0; option_pools.len()],
+        option_pools,
+        total_fees: 0,
+        leading_option: None,
+        leading_since: None,
+        resolution_mode: ResolutionMode::Algorithmic,
+        tie_policy: TiePolicy::Void,
+        scoring_weights: ScoringWeights { time: 70, money: 30, participants: 0 },
+        oracle: None,
+        committee: Vec::new(),
+        committee_threshold: 0,
+        commit_deadline: 0,
+        reveal_deadline: 0,
+        phase: MarketPhase::Resolved,
+        winner: Some(winner),
+        winning_options: vec![winner],
+        paused: false,
+        bump: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Deterministic pseudo-random stakes so the invariant runs over many shapes
+    fn split_stakes(total: u64, parts: usize, seed: u64) -> Vec<u64> {
+        let mut state = seed;
+        let mut weights = Vec::with_capacity(parts);
+        for _ in 0..parts {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            weights.push((state >> 33) % 1_000 + 1);
+        }
+        let weight_sum: u64 = weights.iter().sum();
+        let mut stakes: Vec<u64> = weights.iter().map(|w| total * w / weight_sum).collect();
+        let assigned: u64 = stakes.iter().sum();
+        stakes[0] += total - assigned;
+        stakes
+    }
+    
+    #[test]
+    fn payouts_never_exceed_pool() {
+        for seed in 0..200u64 {
+            let winning_pool = 1_000_000 + seed * 7_919_333;
+            let losing_pool = 3_000_000 + seed * 104_729;
+            let market = resolved_market(vec![winning_pool, losing_pool], 0);
+            
+            let stakes = split_stakes(winning_pool, 1 + (seed % 17) as usize, seed);
+            let paid: u64 = stakes
+                .iter()
+                .map(|stake| market.calculate_payout(*stake).unwrap())
+                .sum();
+            
+            assert!(paid <= market.total_pool);
+            // Rounding dust is at most one lamport per winner
+            assert!(market.total_pool - paid < stakes.len() as u64);
+        }
+    }
+    
+    #[test]
+    fn sole_winner_takes_whole_pool() {
+        let market = resolved_market(vec![4_987_500, 9_975_000, 1_000], 1);
+        assert_eq!(market.calculate_payout(9_975_000).unwrap(), market.total_pool);
+    }
+    
+    #[test]
+    fn lmsr_shares_pay_one_unit_each() {
+        let mut market = resolved_market(vec![3_000, 1_000], 0);
+        market.pricing = PricingMode::Lmsr { liquidity: 1_000 };
+        assert_eq!(market.calculate_payout(3_000).unwrap(), 3_000);
+        
+        market.winning_options = vec![0, 1];
+        assert_eq!(market.calculate_payout(1_000).unwrap(), 500);
+    }
+    
+    #[test]
+    fn liquidity_providers_are_paid_before_closing() {
+        let mut market = resolved_market(vec![3_000, 1_000], 0);
+        market.pricing = PricingMode::Cpmm { fee_bps: 100 };
+        market.amm = Some(AmmPool { reserves: [500, 2_500], lp_supply: 1_000 });
+        market.outstanding_claims = 0;
+        assert!(!market.is_closable(100, 50));
+        assert!(!market.is_closable(100, 100));
+        
+        market.phase = MarketPhase::Cancelled;
+        assert!(!market.is_closable(100, 100));
+        
+        market.phase = MarketPhase::Resolved;
+        market.cpmm_pool().unwrap().lp_supply = 0;
+        assert!(market.is_closable(100, 50));
+    }
+    
+    #[test]
+    fn cpmm_trades_move_only_the_pool() {
+        let mut market = resolved_market(vec![0, 0], 0);
+        market.phase = MarketPhase::Betting;
+        market.pricing = PricingMode::Cpmm { fee_bps: 0 };
+        assert!(market.buy_cost(0, 100).is_err());
+        
+        market.amm = Some(AmmPool { reserves: [1_000_000, 1_000_000], lp_supply: 1_000_000 });
+        let cost = market.buy_cost(0, 400_000).unwrap();
+        assert_eq!(market.amm.as_ref().unwrap().reserves, [600_000 + cost, 1_000_000 + cost]);
+        assert_eq!(market.option_pools, vec![0, 0]);
+        assert!(market.prices_bps()[0] > 5_000);
+    }
+    
+    #[test]
+    fn stake_larger_than_winning_pool_is_rejected() {
+        let market = resolved_market(vec![100, 200], 0);
+        assert!(market.calculate_payout(101).is_err());
+    }
+    
+    fn at(unix_timestamp: i64) -> Clock {
+        Clock { unix_timestamp, ..Clock::default() }
+    }
+    
+    #[test]
+    fn shares_pay_out_only_once_settled() {
+        let mut market = resolved_market(vec![100, 200, 300], 1);
+        assert_eq!(market.share_payout(1, 1_000), 1_000);
+        assert_eq!(market.share_payout(0, 1_000), 0);
+        
+        // Split pot: a complete set still pays exactly one unit
+        market.winning_options = vec![0, 1];
+        assert_eq!(market.share_payout(0, 1_000) + market.share_payout(1, 1_000), 1_000);
+        assert_eq!(market.share_payout(2, 1_000), 0);
+        
+        market.phase = MarketPhase::Cancelled;
+        assert_eq!(market.share_payout(2, 900), 300);
+        
+        market.phase = MarketPhase::Betting;
+        assert_eq!(market.share_payout(1, 1_000), 0);
+    }
+    
+    #[test]
+    fn cancelled_market_is_closable_once_refunded() {
+        let mut market = resolved_market(vec![100, 200], 0);
+        market.phase = MarketPhase::Cancelled;
+        market.resolution_time = Some(1_000);
+        assert!(!market.is_closable(500, 1_200));
+        assert!(market.is_closable(500, 1_500));
         
-        if self.leading_option != Some(leader) {
-            self.leading_option = Some(leader);
-            self.leading_since = Some(clock.unix_timestamp);
+        market.option_pools = vec![0, 0];
+        market.total_pool = 0;
+        assert!(market.is_closable(500, 1_200));
+    }
+    
+    #[test]
+    fn unredeemed_shares_block_closing_after_the_window() {
+        let mut market = resolved_market(vec![100, 200], 0);
+        market.outstanding_claims = 0;
+        market.resolution_time = Some(1_000);
+        market.share_collateral = 50;
+        assert!(!market.is_closable(500, 1_200));
+        assert!(!market.is_closable(500, 1_500));
+        
+        market.phase = MarketPhase::Cancelled;
+        assert!(!market.is_closable(500, 1_500));
+        
+        market.share_collateral = 0;
+        assert!(market.is_closable(500, 1_500));
+    }
+    
+    #[test]
+    fn leadership_time_accumulates_across_lead_changes() {
+        let mut market = resolved_market(vec![0, 0], 0);
+        market.end_time = 1_000;
+        
+        // Option 0 leads for 900s, then option 1 takes over for the last 100s
+        market.option_pools = vec![10, 0];
+        market.update_leader(&at(0));
+        market.option_pools = vec![10, 20];
+        market.update_leader(&at(900));
+        
+        assert_eq!(market.leadership_time(0, 1_000), 900);
+        assert_eq!(market.leadership_time(1, 1_000), 100);
+        
+        // Option 0 regains the lead: its earlier stint is kept
+        market.option_pools = vec![30, 20];
+        market.update_leader(&at(950));
+        assert_eq!(market.leadership_time(0, 1_000), 950);
+        assert_eq!(market.leadership_time(1, 1_000), 50);
+    }
+    
+    #[test]
+    fn unchanged_leader_is_not_double_counted() {
+        let mut market = resolved_market(vec![0, 0], 0);
+        market.option_pools = vec![10, 0];
+        market.update_leader(&at(100));
+        market.option_pools = vec![20, 5];
+        market.update_leader(&at(400));
+        
+        assert_eq!(market.leadership_time(0, 500), 400);
+        assert_eq!(market.leadership_seconds, vec![0, 0]);
+    }
+    
+    #[test]
+    fn equal_pool_keeps_the_incumbent_leader() {
+        let mut market = resolved_market(vec![0, 0, 0], 0);
+        market.option_pools = vec![0, 10, 0];
+        market.update_leader(&at(100));
+        market.option_pools = vec![10, 10, 10];
+        market.update_leader(&at(200));
+        assert_eq!(market.leading_option, Some(1));
+        assert_eq!(market.leading_since, Some(100));
+        
+        market.option_pools = vec![0, 0, 0];
+        market.update_leader(&at(300));
+        assert_eq!(market.leading_option, None);
+        assert_eq!(market.leadership_time(1, 1_000), 200);
+    }
+    
+    #[test]
+    fn tie_policies() {
+        let mut market = resolved_market(vec![50, 50, 50], 0);
+        market.pool_updated_at = vec![30, 10, 10];
+        
+        market.tie_policy = TiePolicy::Void;
+        assert_eq!(market.apply_tie_policy(&[0, 2]), None);
+        assert_eq!(market.apply_tie_policy(&[2]), Some(vec![2]));
+        
+        market.tie_policy = TiePolicy::SplitPot;
+        assert_eq!(market.apply_tie_policy(&[0, 2]), Some(vec![0, 2]));
+        
+        // Option 0 was topped up last; 1 and 2 reached their pools together
+        market.tie_policy = TiePolicy::EarliestToPool;
+        assert_eq!(market.apply_tie_policy(&[0, 1, 2]), Some(vec![1]));
+        assert_eq!(market.apply_tie_policy(&[0, 2]), Some(vec![2]));
+    }
+    
+    #[test]
+    fn split_pot_pays_tied_options_pro_rata() {
+        let mut market = resolved_market(vec![300, 300, 400], 0);
+        market.resolve(&[0, 1], 0);
+        assert_eq!(market.winning_pool(), 600);
+        assert_eq!(market.outstanding_claims, 600);
+        
+        let paid = market.calculate_payout(100).unwrap()
+            + market.calculate_payout(200).unwrap()
+            + market.calculate_payout(300).unwrap();
+        assert_eq!(market.calculate_payout(300).unwrap(), 500);
+        assert!(paid <= market.total_pool);
+    }
+    
+    #[test]
+    fn scoring_weights_decide_between_time_money_and_crowd() {
+        // Option 0: led the whole market with one whale.
+        // Option 1: bigger pool from many small bettors, never led.
+        let mut market = resolved_market(vec![400, 600], 0);
+        market.start_time = 0;
+        market.end_time = 1_000;
+        market.leading_option = Some(0);
+        market.leading_since = Some(0);
+        market.option_bettors = vec![1, 9];
+        
+        market.scoring_weights = ScoringWeights { time: 70, money: 30, participants: 0 };
+        assert!(market.score(0, 1_000) > market.score(1, 1_000));
+        
+        market.scoring_weights = ScoringWeights { time: 0, money: 100, participants: 0 };
+        assert!(market.score(1, 1_000) > market.score(0, 1_000));
+        
+        market.scoring_weights = ScoringWeights { time: 40, money: 0, participants: 60 };
+        assert_eq!(market.score(0, 1_000), 100 * 40 + 10 * 60);
+        assert_eq!(market.score(1, 1_000), 90 * 60);
+    }
+    
+    #[test]
+    fn scoring_weights_respect_bounds() {
+        let min = ScoringWeights { time: 50, money: 0, participants: 0 };
+        let max = ScoringWeights { time: 100, money: 50, participants: 20 };
+        
+        assert!(ScoringWeights { time: 70, money: 30, participants: 0 }.is_within(&min, &max));
+        assert!(ScoringWeights { time: 50, money: 30, participants: 20 }.is_within(&min, &max));
+        assert!(!ScoringWeights { time: 40, money: 50, participants: 10 }.is_within(&min, &max));
+        assert!(!ScoringWeights { time: 60, money: 10, participants: 30 }.is_within(&min, &max));
+    }
+    
+    #[test]
+    fn voting_stages_follow_the_clock() {
+        let mut market = resolved_market(vec![100, 200], 0);
+        market.end_time = 1_000;
+        market.commit_deadline = 1_600;
+        market.reveal_deadline = 2_200;
+        
+        assert_eq!(market.voting_stage(999), VotingStage::Betting);
+        assert_eq!(market.voting_stage(1_000), VotingStage::Commit);
+        assert_eq!(market.voting_stage(1_599), VotingStage::Commit);
+        assert_eq!(market.voting_stage(1_600), VotingStage::Reveal);
+        assert_eq!(market.voting_stage(2_199), VotingStage::Reveal);
+        assert_eq!(market.voting_stage(2_200), VotingStage::Tally);
+    }
+    
+    #[test]
+    fn odd_share_batches_redeem_all_collateral() {
+        let batches = [(0, 1), (2, 1), (1, 3), (0, 7), (2, 500), (0, 333), (1, 998), (2, 500), (0, 660)];
+        for (phase, winners) in [(MarketPhase::Cancelled, vec![0]), (MarketPhase::Resolved, vec![0, 2])] {
+            let mut market = resolved_market(vec![100, 200, 300], 0);
+            market.phase = phase;
+            market.winning_options = winners;
+            market.share_collateral = 1_001;
+            market.share_supply = vec![1_001; 3];
+            let redeemable = if market.phase == MarketPhase::Cancelled { 3 } else { 2 };
+            
+            let mut paid = 0;
+            for (option, amount) in batches {
+                if market.phase == MarketPhase::Resolved && option == 1 {
+                    assert!(market.redeem_shares(option, amount).is_err());
+                    continue;
+                }
+                // Never worth less than flooring each batch on its own
+                let payout = market.redeem_shares(option, amount).unwrap();
+                assert!(payout >= amount / redeemable);
+                paid += payout;
+            }
+            
+            assert_eq!(paid, 1_001);
+            assert_eq!(market.share_collateral, 0);
+            assert!(market.redeem_shares(0, 1).is_err());
         }
     }
-}
\ No newline at end of file
+}
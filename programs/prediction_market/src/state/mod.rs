@@ -2,6 +2,14 @@
 
 pub mod market;
 pub mod user_bet;
+pub mod fee_vault;
+pub mod platform_config;
+pub mod resolution_proposal;
+pub mod resolution_votes;
 
 pub use market::*;
-pub use user_bet::*;
\ No newline at end of file
+pub use user_bet::*;
+pub use fee_vault::*;
+pub use platform_config::*;
+pub use resolution_proposal::*;
+pub use resolution_votes::*;
\ No newline at end of file
@@ -0,0 +1,178 @@
+// programs/prediction_market/state/platform_config.rs
+
+use anchor_lang::prelude::*;
+use crate::errors::*;
+use crate::state::ScoringWeights;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    // Yetki
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub treasury: Pubkey,
+    
+    // Komisyonlar
+    pub base_commission_bps: u16,
+    pub late_commission_bps: u16,
+    pub early_bet_threshold: u64, // Süre yüzdesi
+    pub early_exit_penalty_bps: u16,
+    pub late_exit_penalty_bps: u16,
+    
+    // Limitler
+    pub min_bet_amount: u64,
+    pub min_velocity: u64,
+    pub velocity_factor: u64,
+    pub min_token_bet: u64,      // SPL marketler için, TOKEN_AMOUNT_DECIMALS ile
+    pub min_token_velocity: u64, // SPL marketler için, TOKEN_AMOUNT_DECIMALS ile
+    
+    // Süreler
+    pub min_market_duration: i64,
+    pub max_market_duration: i64,
+    pub claim_window: i64, // Çözümden sonra kazançların talep süresi
+    
+    // İyimser çözüm
+    pub resolution_bond: u64,    // Öneri ve itiraz için yatırılan teminat
+    pub dispute_window: i64,     // Öneriden sonra itiraz süresi
+    pub settlement_timeout: i64, // İtirazın yönetici tarafından çözülmesi gereken süre
+    
+    // Komite oylaması
+    pub commit_window: i64, // Market bitiminden sonra gizli oy süresi
+    pub reveal_window: i64, // Gizli oyların açıklanma süresi
+    
+    // Oracle
+    pub oracle_program: Pubkey, // Fiyat feed hesaplarının sahibi olması gereken program (Pyth)
+    
+    // Algoritmik skor ağırlık sınırları (yüzde)
+    pub min_scoring_weights: ScoringWeights,
+    pub max_scoring_weights: ScoringWeights,
+    
+    // Platform
+    pub create_market_fee: u64,
+    
+    // PDA bump
+    pub bump: u8,
+}
+
+/// Tunable platform parameters, passed to `initialize_config` and `update_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub treasury: Pubkey,
+    pub base_commission_bps: u16,
+    pub late_commission_bps: u16,
+    pub early_bet_threshold: u64,
+    pub early_exit_penalty_bps: u16,
+    pub late_exit_penalty_bps: u16,
+    pub min_bet_amount: u64,
+    pub min_velocity: u64,
+    pub velocity_factor: u64,
+    pub min_token_bet: u64,
+    pub min_token_velocity: u64,
+    pub min_market_duration: i64,
+    pub max_market_duration: i64,
+    pub claim_window: i64,
+    pub resolution_bond: u64,
+    pub dispute_window: i64,
+    pub settlement_timeout: i64,
+    pub commit_window: i64,
+    pub reveal_window: i64,
+    pub oracle_program: Pubkey,
+    pub min_scoring_weights: ScoringWeights,
+    pub max_scoring_weights: ScoringWeights,
+    pub create_market_fee: u64,
+}
+
+impl PlatformConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+    
+    pub fn apply(&mut self, params: ConfigParams) -> Result<()> {
+        // A bet must always leave a non-zero stake in the pool
+        require!(
+            params.base_commission_bps < 10_000 && params.late_commission_bps < 10_000,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.early_exit_penalty_bps <= 10_000 && params.late_exit_penalty_bps <= 10_000,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.early_bet_threshold <= 100,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.min_bet_amount > 0 && params.min_token_bet > 0 && params.velocity_factor > 0,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.min_market_duration > 0
+                && params.min_market_duration <= params.max_market_duration,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.claim_window > 0,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.resolution_bond > 0
+                && params.dispute_window > 0
+                && params.settlement_timeout > 0,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.commit_window > 0 && params.reveal_window > 0,
+            PredictionMarketError::InvalidConfig
+        );
+        require!(
+            params.oracle_program != Pubkey::default(),
+            PredictionMarketError::InvalidConfig
+        );
+        // Bounds must leave room for weights that sum to 100
+        let (min, max) = (&params.min_scoring_weights, &params.max_scoring_weights);
+        require!(
+            min.is_within(min, max) && min.total() <= 100 && max.total() >= 100,
+            PredictionMarketError::InvalidConfig
+        );
+        
+        self.treasury = params.treasury;
+        self.base_commission_bps = params.base_commission_bps;
+        self.late_commission_bps = params.late_commission_bps;
+        self.early_bet_threshold = params.early_bet_threshold;
+        self.early_exit_penalty_bps = params.early_exit_penalty_bps;
+        self.late_exit_penalty_bps = params.late_exit_penalty_bps;
+        self.min_bet_amount = params.min_bet_amount;
+        self.min_velocity = params.min_velocity;
+        self.velocity_factor = params.velocity_factor;
+        self.min_token_bet = params.min_token_bet;
+        self.min_token_velocity = params.min_token_velocity;
+        self.min_market_duration = params.min_market_duration;
+        self.max_market_duration = params.max_market_duration;
+        self.claim_window = params.claim_window;
+        self.resolution_bond = params.resolution_bond;
+        self.dispute_window = params.dispute_window;
+        self.settlement_timeout = params.settlement_timeout;
+        self.commit_window = params.commit_window;
+        self.reveal_window = params.reveal_window;
+        self.oracle_program = params.oracle_program;
+        self.min_scoring_weights = params.min_scoring_weights;
+        self.max_scoring_weights = params.max_scoring_weights;
+        self.create_market_fee = params.create_market_fee;
+        
+        Ok(())
+    }
+    
+    pub fn commission_bps(&self, elapsed_percentage: u64) -> u16 {
+        if elapsed_percentage <= self.early_bet_threshold {
+            self.base_commission_bps
+        } else {
+            self.late_commission_bps
+        }
+    }
+    
+    pub fn exit_penalty_bps(&self, elapsed_percentage: u64) -> u16 {
+        if elapsed_percentage <= self.early_bet_threshold {
+            self.early_exit_penalty_bps
+        } else {
+            self.late_exit_penalty_bps
+        }
+    }
+}
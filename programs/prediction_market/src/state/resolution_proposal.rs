@@ -0,0 +1,113 @@
+// programs/prediction_market/state/resolution_proposal.rs
+
+use anchor_lang::prelude::*;
+
+/// Outcome proposed for an optimistic market. Holds the proposer's bond and,
+/// once disputed, the matching bond of the disputer.
+#[account]
+#[derive(InitSpace)]
+pub struct ResolutionProposal {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_option: u8,
+    pub bond: u64, // Her iki taraf için aynı teminat
+    
+    // Zaman
+    pub proposed_at: i64,
+    pub dispute_deadline: i64,
+    
+    // İtiraz
+    pub disputer: Option<Pubkey>,
+    pub disputed_at: Option<i64>,
+    pub settlement_deadline: Option<i64>, // Yönetici bu zamana kadar çözmezse market iptal edilebilir
+    
+    // PDA bump
+    pub bump: u8,
+}
+
+impl ResolutionProposal {
+    pub const SEED_PREFIX: &'static [u8] = b"proposal";
+    
+    pub fn can_dispute(&self, now: i64) -> bool {
+        self.disputer.is_none() && now < self.dispute_deadline
+    }
+    
+    /// Undisputed proposals become final once the dispute window has passed.
+    pub fn can_finalize(&self, now: i64) -> bool {
+        self.disputer.is_none() && now >= self.dispute_deadline
+    }
+    
+    /// The admin can settle a dispute until its settlement deadline.
+    pub fn can_settle(&self, now: i64) -> bool {
+        self.settlement_deadline.is_some_and(|deadline| now < deadline)
+    }
+    
+    /// A dispute left unsettled past its deadline can void the market.
+    pub fn can_expire(&self, now: i64) -> bool {
+        self.settlement_deadline.is_some_and(|deadline| now >= deadline)
+    }
+    
+    /// Party that takes both bonds once a dispute is settled on `outcome`.
+    pub fn bond_recipient(&self, outcome: u8) -> Option<Pubkey> {
+        let disputer = self.disputer?;
+        if outcome == self.proposed_option {
+            Some(self.proposer)
+        } else {
+            Some(disputer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn proposal(disputer: Option<Pubkey>) -> ResolutionProposal {
+        ResolutionProposal {
+            market: Pubkey::default(),
+            proposer: Pubkey::new_unique(),
+            proposed_option: 1,
+            bond: 1_000,
+            proposed_at: 100,
+            dispute_deadline: 200,
+            disputer,
+            disputed_at: disputer.map(|_| 150),
+            settlement_deadline: disputer.map(|_| 300),
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn dispute_window_boundaries() {
+        let open = proposal(None);
+        assert!(open.can_dispute(199));
+        assert!(!open.can_finalize(199));
+        assert!(!open.can_dispute(200));
+        assert!(open.can_finalize(200));
+        
+        let disputed = proposal(Some(Pubkey::new_unique()));
+        assert!(!disputed.can_dispute(150));
+        assert!(!disputed.can_finalize(10_000));
+    }
+    
+    #[test]
+    fn unsettled_disputes_expire_at_the_deadline() {
+        let disputed = proposal(Some(Pubkey::new_unique()));
+        assert!(disputed.can_settle(299));
+        assert!(!disputed.can_expire(299));
+        assert!(!disputed.can_settle(300));
+        assert!(disputed.can_expire(300));
+        
+        let open = proposal(None);
+        assert!(!open.can_settle(150) && !open.can_expire(10_000));
+    }
+    
+    #[test]
+    fn bonds_go_to_the_side_that_was_right() {
+        let disputer = Pubkey::new_unique();
+        let disputed = proposal(Some(disputer));
+        assert_eq!(disputed.bond_recipient(1), Some(disputed.proposer));
+        assert_eq!(disputed.bond_recipient(0), Some(disputer));
+        assert_eq!(proposal(None).bond_recipient(1), None);
+    }
+}
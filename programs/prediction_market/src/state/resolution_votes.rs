@@ -0,0 +1,172 @@
+// programs/prediction_market/state/resolution_votes.rs
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::constants::*;
+use crate::errors::*;
+
+/// Committee ballots for a market, indexed like `Market::committee`.
+/// Members commit `hash(option || salt)` with a bond and reveal it later;
+/// the account holds the bonds of every unrevealed commitment.
+#[account]
+#[derive(InitSpace)]
+pub struct ResolutionVotes {
+    pub market: Pubkey,
+    pub opened_by: Pubkey, // Hesap kirasını ödeyen ilk üye
+    pub bond: u64,         // Üye başına teminat
+    #[max_len(MAX_COMMITTEE_MEMBERS)]
+    pub commitments: Vec<Option<[u8; 32]>>, // Üye başına gizli oy
+    #[max_len(MAX_COMMITTEE_MEMBERS)]
+    pub votes: Vec<Option<u8>>, // Üye başına açıklanan oy
+    #[max_len(MAX_OPTIONS)]
+    pub tallies: Vec<u8>,       // Seçenek başına oy sayısı
+    pub bump: u8,
+}
+
+/// Commitment a member submits for `option_index`.
+pub fn vote_commitment(option_index: u8, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[option_index], salt]).to_bytes()
+}
+
+impl ResolutionVotes {
+    pub const SEED_PREFIX: &'static [u8] = b"votes";
+    
+    pub fn commit(&mut self, member_index: usize, commitment: [u8; 32]) -> Result<()> {
+        require!(
+            self.commitments[member_index].is_none(),
+            PredictionMarketError::AlreadyVoted
+        );
+        
+        self.commitments[member_index] = Some(commitment);
+        
+        Ok(())
+    }
+    
+    /// Checks a reveal against the member's commitment, records the vote
+    /// and returns the new tally of that option.
+    pub fn reveal(&mut self, member_index: usize, option_index: u8, salt: &[u8; 32]) -> Result<u8> {
+        let commitment = self.commitments[member_index]
+            .ok_or(PredictionMarketError::NoCommitment)?;
+        require!(
+            self.votes[member_index].is_none(),
+            PredictionMarketError::AlreadyVoted
+        );
+        require!(
+            vote_commitment(option_index, salt) == commitment,
+            PredictionMarketError::InvalidReveal
+        );
+        
+        self.votes[member_index] = Some(option_index);
+        let tally = &mut self.tallies[option_index as usize];
+        *tally = tally.checked_add(1).ok_or(PredictionMarketError::MathOverflow)?;
+        
+        Ok(*tally)
+    }
+    
+    pub fn votes_cast(&self) -> u8 {
+        self.votes.iter().filter(|vote| vote.is_some()).count() as u8
+    }
+    
+    /// Members who committed but never revealed; their bonds are slashed.
+    pub fn unrevealed(&self) -> u8 {
+        self.commitments
+            .iter()
+            .zip(&self.votes)
+            .filter(|(commitment, vote)| commitment.is_some() && vote.is_none())
+            .count() as u8
+    }
+    
+    /// The option that reached `threshold`, if exactly one did.
+    pub fn decided_option(&self, threshold: u8) -> Option<u8> {
+        let mut reached = self.tallies
+            .iter()
+            .enumerate()
+            .filter(|(_, tally)| **tally >= threshold)
+            .map(|(i, _)| i as u8);
+        
+        match (reached.next(), reached.next()) {
+            (Some(option), None) => Some(option),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn ballots(members: usize, options: usize) -> ResolutionVotes {
+        ResolutionVotes {
+            market: Pubkey::default(),
+            opened_by: Pubkey::default(),
+            bond: 1_000,
+            commitments: vec![None; members],
+            votes: vec![None; members],
+            tallies: vec![0; options],
+            bump: 0,
+        }
+    }
+    
+    fn commit_and_reveal(votes: &mut ResolutionVotes, member: usize, option: u8) -> u8 {
+        let salt = [member as u8 + 1; 32];
+        votes.commit(member, vote_commitment(option, &salt)).unwrap();
+        votes.reveal(member, option, &salt).unwrap()
+    }
+    
+    #[test]
+    fn option_is_decided_at_threshold() {
+        let mut votes = ballots(3, 2);
+        assert_eq!(commit_and_reveal(&mut votes, 0, 1), 1);
+        assert_eq!(votes.decided_option(2), None);
+        assert_eq!(commit_and_reveal(&mut votes, 2, 1), 2);
+        assert_eq!(votes.decided_option(2), Some(1));
+        assert_eq!(votes.votes_cast(), 2);
+    }
+    
+    #[test]
+    fn members_commit_and_reveal_once() {
+        let mut votes = ballots(3, 2);
+        let salt = [7u8; 32];
+        votes.commit(1, vote_commitment(0, &salt)).unwrap();
+        assert!(votes.commit(1, vote_commitment(1, &salt)).is_err());
+        
+        votes.reveal(1, 0, &salt).unwrap();
+        assert!(votes.reveal(1, 0, &salt).is_err());
+        assert_eq!(votes.tallies, vec![1, 0]);
+    }
+    
+    #[test]
+    fn reveal_must_match_commitment() {
+        let mut votes = ballots(2, 3);
+        let salt = [9u8; 32];
+        votes.commit(0, vote_commitment(2, &salt)).unwrap();
+        
+        // Different option or different salt
+        assert!(votes.reveal(0, 1, &salt).is_err());
+        assert!(votes.reveal(0, 2, &[8u8; 32]).is_err());
+        // Nothing committed
+        assert!(votes.reveal(1, 2, &salt).is_err());
+        
+        assert_eq!(votes.reveal(0, 2, &salt).unwrap(), 1);
+    }
+    
+    #[test]
+    fn unrevealed_commitments_are_counted_for_slashing() {
+        let mut votes = ballots(4, 2);
+        commit_and_reveal(&mut votes, 0, 0);
+        votes.commit(1, vote_commitment(1, &[1u8; 32])).unwrap();
+        votes.commit(3, vote_commitment(0, &[3u8; 32])).unwrap();
+        
+        assert_eq!(votes.unrevealed(), 2);
+        assert_eq!(votes.votes_cast(), 1);
+    }
+    
+    #[test]
+    fn split_vote_decides_nothing() {
+        let mut votes = ballots(4, 2);
+        commit_and_reveal(&mut votes, 0, 0);
+        commit_and_reveal(&mut votes, 1, 1);
+        assert_eq!(votes.decided_option(1), None);
+        assert_eq!(votes.decided_option(2), None);
+    }
+}
@@ -1,14 +1,21 @@
 // programs/prediction_market/state/user_bet.rs
 
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::*;
 
+/// A user's position in one market. Parimutuel bets count collateral,
+/// LMSR and CPMM bets count shares, which carry no commission, so
+/// `amount` and `net_amount` are both the share total there.
 #[account]
 #[derive(InitSpace)]
 pub struct UserBet {
     pub user: Pubkey,
     pub market: Pubkey,
-    pub option_index: u8,
-    pub amount: u64,
+    #[max_len(MAX_OPTIONS)]
+    pub positions: Vec<u64>, // Her seçenek için net bahis ya da pay adedi
+    pub amount: u64,         // Parimutuel: komisyon dahil yatırılan toplam miktar, LMSR/CPMM: toplam pay
+    pub net_amount: u64,     // Havuza giren toplam miktar (positions toplamı)
     pub placed_at: i64,
     pub claimed: bool,
     pub bump: u8,
@@ -16,4 +23,98 @@ pub struct UserBet {
 
 impl UserBet {
     pub const SEED_PREFIX: &'static [u8] = b"user_bet";
-}
\ No newline at end of file
+    
+    /// Net stake on a single option, zero if the user never backed it.
+    pub fn position(&self, option_index: u8) -> u64 {
+        self.positions
+            .get(option_index as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+    
+    /// Net stake across the winning option(s) of a resolved market.
+    pub fn winning_stake(&self, winning_options: &[u8]) -> u64 {
+        winning_options
+            .iter()
+            .map(|option| self.position(*option))
+            .sum()
+    }
+    
+    /// Commission paid on top of a parimutuel stake, held in the fee vault.
+    /// Always zero for shares.
+    pub fn commission(&self) -> u64 {
+        self.amount.saturating_sub(self.net_amount)
+    }
+    
+    /// Removes `amount` of net stake from an option and returns the gross
+    /// amount (stake plus commission) it accounted for. Withdrawing the last
+    /// of the stake takes the whole remainder, so no commission is left on
+    /// an empty bet.
+    pub fn withdraw(&mut self, option_index: u8, amount: u64) -> Result<u64> {
+        require!(
+            amount > 0 && amount <= self.position(option_index),
+            PredictionMarketError::InsufficientPosition
+        );
+        
+        let gross_amount = if amount == self.net_amount {
+            self.amount
+        } else {
+            (amount as u128 * self.amount as u128 / self.net_amount as u128) as u64
+        };
+        
+        self.positions[option_index as usize] -= amount;
+        self.net_amount -= amount;
+        self.amount -= gross_amount;
+        
+        Ok(gross_amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn partial_withdrawals_leave_nothing_on_an_empty_bet() {
+        // 1_000_003 staked on two options with 0.25% commission, leaving
+        // a fractional commission per lamport that floors on every withdrawal
+        let mut bet = UserBet {
+            user: Pubkey::default(),
+            market: Pubkey::default(),
+            positions: vec![600_001, 400_002],
+            amount: 1_002_510,
+            net_amount: 1_000_003,
+            placed_at: 0,
+            claimed: false,
+            bump: 0,
+        };
+        
+        let mut withdrawn = 0u64;
+        for (option, amount) in [(0, 7), (1, 333_333), (0, 1), (0, 599_993), (1, 66_669)] {
+            withdrawn += bet.withdraw(option, amount).unwrap();
+            assert!(bet.commission() <= bet.amount);
+        }
+        
+        assert_eq!(bet.positions, vec![0, 0]);
+        assert_eq!((bet.net_amount, bet.amount, bet.commission()), (0, 0, 0));
+        assert_eq!(withdrawn, 1_002_510);
+        assert!(bet.withdraw(0, 1).is_err());
+    }
+    
+    #[test]
+    fn share_bets_carry_no_commission() {
+        // buy_shares keeps `amount` and `net_amount` equal to the share total
+        let bet = UserBet {
+            user: Pubkey::default(),
+            market: Pubkey::default(),
+            positions: vec![2_500, 0, 700],
+            amount: 3_200,
+            net_amount: 3_200,
+            placed_at: 0,
+            claimed: false,
+            bump: 0,
+        };
+        assert_eq!(bet.commission(), 0);
+        assert_eq!(bet.winning_stake(&[0, 2]), bet.amount);
+    }
+}
@@ -0,0 +1,160 @@
+// programs/prediction_market/utils.rs
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Lamports an account can pay out without dropping below its rent-exempt minimum.
+pub fn available_lamports(info: &AccountInfo, rent: &Rent) -> u64 {
+    info.lamports()
+        .saturating_sub(rent.minimum_balance(info.data_len()))
+}
+
+/// Moves lamports out of a program-owned account (market PDA, fee vault, ...).
+/// Every payout path goes through here so the source stays rent-exempt.
+pub fn transfer_lamports(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    amount: u64,
+    rent: &Rent,
+) -> Result<()> {
+    require!(
+        amount <= available_lamports(from, rent),
+        PredictionMarketError::InsufficientMarketFunds
+    );
+    
+    let to_balance = to.lamports()
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    
+    Ok(())
+}
+
+/// Sends everything above the rent-exempt minimum to the treasury.
+pub fn sweep_to_treasury(
+    from: &AccountInfo,
+    treasury: &AccountInfo,
+    rent: &Rent,
+) -> Result<u64> {
+    let excess = available_lamports(from, rent);
+    transfer_lamports(from, treasury, excess, rent)?;
+    
+    Ok(excess)
+}
+
+/// Settles a cancelled market once every stake is refunded: leftover fees go
+/// to the treasury, the market and fee vault rent back to the creator. What
+/// is left of a creator-funded subsidy stays in the market for the creator.
+pub fn close_refunded_market<'info>(
+    market: &Account<'info, Market>,
+    fee_vault: &Account<'info, FeeVault>,
+    treasury: &AccountInfo<'info>,
+    creator: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    if market.subsidy == 0 {
+        sweep_to_treasury(&market.to_account_info(), treasury, &rent)?;
+    }
+    sweep_to_treasury(&fee_vault.to_account_info(), treasury, &rent)?;
+    
+    fee_vault.close(creator.clone())?;
+    market.close(creator.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const DATA_LEN: usize = 128;
+    
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+    
+    impl TestAccount {
+        fn new(lamports: u64, data_len: usize) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner: crate::ID,
+                lamports,
+                data: vec![0; data_len],
+            }
+        }
+        
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+    
+    #[test]
+    fn pays_out_everything_above_rent() {
+        let rent = Rent::default();
+        let minimum = rent.minimum_balance(DATA_LEN);
+        let mut market = TestAccount::new(minimum + 1_000, DATA_LEN);
+        let mut user = TestAccount::new(0, 0);
+        
+        transfer_lamports(&market.info(), &user.info(), 1_000, &rent).unwrap();
+        
+        assert_eq!(market.lamports, minimum);
+        assert_eq!(user.lamports, 1_000);
+    }
+    
+    #[test]
+    fn rejects_debit_into_rent_reserve() {
+        let rent = Rent::default();
+        let minimum = rent.minimum_balance(DATA_LEN);
+        let mut market = TestAccount::new(minimum + 1_000, DATA_LEN);
+        let mut user = TestAccount::new(0, 0);
+        
+        let result = transfer_lamports(&market.info(), &user.info(), 1_001, &rent);
+        
+        assert_eq!(result.unwrap_err(), PredictionMarketError::InsufficientMarketFunds.into());
+        assert_eq!(market.lamports, minimum + 1_000);
+        assert_eq!(user.lamports, 0);
+    }
+    
+    #[test]
+    fn underfunded_account_has_nothing_available() {
+        let rent = Rent::default();
+        let mut market = TestAccount::new(rent.minimum_balance(DATA_LEN) - 1, DATA_LEN);
+        
+        assert_eq!(available_lamports(&market.info(), &rent), 0);
+    }
+    
+    #[test]
+    fn rounding_dust_stays_in_market() {
+        let rent = Rent::default();
+        let minimum = rent.minimum_balance(DATA_LEN);
+        
+        // 100 lamports split between three equal winners: 33 each, 1 lamport of dust
+        let mut resolved = resolved_market(vec![99, 1], 0);
+        
+        let mut market = TestAccount::new(minimum + resolved.total_pool, DATA_LEN);
+        let mut user = TestAccount::new(0, 0);
+        
+        for _ in 0..3 {
+            let payout = resolved.calculate_payout(33).unwrap();
+            transfer_lamports(&market.info(), &user.info(), payout, &rent).unwrap();
+            resolved.outstanding_claims -= 33;
+        }
+        
+        assert_eq!(user.lamports, 99);
+        assert_eq!(market.lamports, minimum + 1);
+        assert_eq!(available_lamports(&market.info(), &rent), 1);
+    }
+}
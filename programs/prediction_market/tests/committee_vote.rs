@@ -0,0 +1,259 @@
+// programs/prediction_market/tests/committee_vote.rs
+//
+// Committee resolution end to end: the program runs natively under
+// solana-program-test and the clock is warped past `end_time`,
+// `commit_deadline` and `reveal_deadline` between steps.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::*;
+use solana_program_test::tokio;
+use solana_sdk::{signature::{Keypair, Signer}, transaction::TransactionError};
+
+impl Env {
+    async fn commit(&mut self, accounts: &MarketAccounts, member: usize, option_index: u8) {
+        let member = self.members[member].insecure_clone();
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::CommitVote {
+                market: accounts.market,
+                votes: accounts.votes,
+                config: config_pda(),
+                member: member.pubkey(),
+                system_program: system_program::ID,
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::CommitVote {
+                commitment: vote_commitment(option_index, &salt(&member)),
+            }
+            .data(),
+        };
+        self.send(instruction, &member).await.unwrap();
+    }
+    
+    async fn reveal(&mut self, accounts: &MarketAccounts, member: usize, option_index: u8) {
+        let member = self.members[member].insecure_clone();
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::RevealVote {
+                market: accounts.market,
+                votes: accounts.votes,
+                member: member.pubkey(),
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::RevealVote { option_index, salt: salt(&member) }.data(),
+        };
+        self.send(instruction, &member).await.unwrap();
+    }
+    
+    async fn finalize(
+        &mut self,
+        accounts: &MarketAccounts,
+        opened_by: Option<Pubkey>,
+    ) -> std::result::Result<(), TransactionError> {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::FinalizeCommitteeVote {
+                market: accounts.market,
+                votes: opened_by.map(|_| accounts.votes),
+                opened_by,
+                fee_vault: accounts.fee_vault,
+                config: config_pda(),
+                treasury: self.treasury,
+                creator: self.creator.pubkey(),
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::FinalizeCommitteeVote {}.data(),
+        };
+        // Permissionless: only the fee payer signs
+        let payer = self.context.payer.insecure_clone();
+        self.send(instruction, &payer).await
+    }
+    
+    async fn update_quorum(
+        &mut self,
+        accounts: &MarketAccounts,
+        threshold: u8,
+    ) -> std::result::Result<(), TransactionError> {
+        let admin = self.admin.insecure_clone();
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::UpdateCommitteeQuorum {
+                market: accounts.market,
+                config: config_pda(),
+                admin: admin.pubkey(),
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::UpdateCommitteeQuorum { threshold }.data(),
+        };
+        self.send(instruction, &admin).await
+    }
+    
+    async fn claim(&mut self, accounts: &MarketAccounts, user: &Keypair) {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::ClaimWinnings {
+                market: accounts.market,
+                user_bet: user_bet_pda(&user.pubkey(), &accounts.market),
+                user: user.pubkey(),
+                collateral_mint: None,
+                vault: None,
+                user_token_account: None,
+                token_program: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::ClaimWinnings {}.data(),
+        };
+        self.send(instruction, user).await.unwrap();
+    }
+}
+
+fn salt(member: &Keypair) -> [u8; 32] {
+    member.pubkey().to_bytes()
+}
+
+#[tokio::test]
+async fn committee_resolves_pays_winners_and_slashes_silent_members() {
+    let mut env = setup().await;
+    let accounts = env.create_market(ResolutionMode::Committee).await;
+    let (alice, bob) = (env.alice.insecure_clone(), env.bob.insecure_clone());
+    
+    env.place_bet(&accounts, &alice, 0).await;
+    env.place_bet(&accounts, &bob, 1).await;
+    
+    let mut members_before = Vec::new();
+    for member in 0..3 {
+        let key = env.members[member].pubkey();
+        members_before.push(env.balance(key).await);
+    }
+    
+    // Commit: members 0 and 1 vote Yes, member 2 commits and goes silent
+    env.warp_to(accounts.end_time).await;
+    for member in 0..3 {
+        env.commit(&accounts, member, 0).await;
+    }
+    assert!(env.market(accounts.market).await.phase == MarketPhase::Resolving);
+    
+    // Reveal: honest members get their bonds back right away
+    let market = env.market(accounts.market).await;
+    env.warp_to(market.commit_deadline).await;
+    env.reveal(&accounts, 0, 0).await;
+    env.reveal(&accounts, 1, 0).await;
+    
+    let member_1 = env.members[1].pubkey();
+    assert_eq!(env.balance(member_1).await, members_before[1]);
+    
+    // Tally only opens at the reveal deadline
+    assert_eq!(
+        env.finalize(&accounts, Some(env.members[0].pubkey())).await.unwrap_err(),
+        custom_error(PredictionMarketError::VotingInProgress)
+    );
+    
+    env.warp_to(market.reveal_deadline).await;
+    let treasury_before = env.balance(env.treasury).await;
+    env.finalize(&accounts, Some(env.members[0].pubkey())).await.unwrap();
+    
+    let market = env.market(accounts.market).await;
+    assert!(market.phase == MarketPhase::Resolved);
+    assert_eq!(market.winning_options, vec![0]);
+    
+    // The silent member's bond goes to the treasury; the ballot rent goes
+    // back to the member who opened it
+    assert_eq!(env.balance(env.treasury).await - treasury_before, BOND);
+    assert!(env.context.banks_client.get_account(accounts.votes).await.unwrap().is_none());
+    for (member, before) in members_before.iter().enumerate() {
+        let key = env.members[member].pubkey();
+        let expected = if member == 2 { before - BOND } else { *before };
+        assert_eq!(env.balance(key).await, expected);
+    }
+    
+    // Alice backed the only winning option and takes both net stakes
+    let alice_bet = user_bet_pda(&alice.pubkey(), &accounts.market);
+    let bet_rent = env.balance(alice_bet).await;
+    let alice_before = env.balance(alice.pubkey()).await;
+    env.claim(&accounts, &alice).await;
+    
+    let net_stake = BET - BET * COMMISSION_BPS / 10_000;
+    assert_eq!(env.balance(alice.pubkey()).await, alice_before + 2 * net_stake + bet_rent);
+}
+
+#[tokio::test]
+async fn committee_without_commitments_voids_for_no_quorum() {
+    let mut env = setup().await;
+    let accounts = env.create_market(ResolutionMode::Committee).await;
+    let (alice, bob) = (env.alice.insecure_clone(), env.bob.insecure_clone());
+    
+    let alice_before = env.balance(alice.pubkey()).await;
+    env.place_bet(&accounts, &alice, 0).await;
+    env.place_bet(&accounts, &bob, 1).await;
+    
+    // Nobody commits, so no ballot exists and the market stays in Betting
+    let market = env.market(accounts.market).await;
+    env.warp_to(market.reveal_deadline - 1).await;
+    assert_eq!(
+        env.finalize(&accounts, None).await.unwrap_err(),
+        custom_error(PredictionMarketError::VotingInProgress)
+    );
+    
+    env.warp_to(market.reveal_deadline).await;
+    env.finalize(&accounts, None).await.unwrap();
+    assert!(env.market(accounts.market).await.phase == MarketPhase::Cancelled);
+    
+    // Stake and commission both come back, along with the bet account rent
+    env.refund(&accounts, &alice).await;
+    assert_eq!(env.balance(alice.pubkey()).await, alice_before);
+    
+    // The last refund closes the market
+    env.refund(&accounts, &bob).await;
+    assert!(env.context.banks_client.get_account(accounts.market).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn quorum_is_fixed_once_a_vote_is_committed() {
+    let mut env = setup().await;
+    let accounts = env.create_market(ResolutionMode::Committee).await;
+    
+    // Still adjustable while the market is open
+    env.update_quorum(&accounts, 3).await.unwrap();
+    assert_eq!(env.market(accounts.market).await.committee_threshold, 3);
+    
+    env.warp_to(accounts.end_time).await;
+    env.commit(&accounts, 0, 0).await;
+    assert_eq!(
+        env.update_quorum(&accounts, 1).await.unwrap_err(),
+        custom_error(PredictionMarketError::VotingInProgress)
+    );
+    assert_eq!(env.market(accounts.market).await.committee_threshold, 3);
+}
+
+#[tokio::test]
+async fn fully_withdrawn_bets_do_not_outlive_a_voided_market() {
+    let mut env = setup().await;
+    let accounts = env.create_market(ResolutionMode::Committee).await;
+    let alice = env.alice.insecure_clone();
+    let alice_bet = user_bet_pda(&alice.pubkey(), &accounts.market);
+    
+    // A full exit closes the bet account right away, betting again reopens it
+    let net_stake = BET - BET * COMMISSION_BPS / 10_000;
+    for _ in 0..2 {
+        env.place_bet(&accounts, &alice, 0).await;
+        assert!(env.context.banks_client.get_account(alice_bet).await.unwrap().is_some());
+        env.withdraw(&accounts, &alice, 0, net_stake).await;
+        assert!(env.context.banks_client.get_account(alice_bet).await.unwrap().is_none());
+    }
+    
+    // The empty market is voided and closed with nothing left behind
+    let market = env.market(accounts.market).await;
+    env.warp_to(market.reveal_deadline).await;
+    env.finalize(&accounts, None).await.unwrap();
+    assert!(env.context.banks_client.get_account(accounts.market).await.unwrap().is_none());
+}
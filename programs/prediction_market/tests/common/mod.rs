@@ -0,0 +1,308 @@
+// programs/prediction_market/tests/common/mod.rs
+//
+// Shared solana-program-test harness: the program runs natively and the
+// clock is warped between steps.
+#![allow(dead_code)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{entrypoint::ProgramResult, instruction::Instruction};
+use anchor_lang::{system_program, AccountSerialize, InstructionData, ToAccountMetas};
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::*;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::InstructionError,
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+pub const BOND: u64 = LAMPORTS_PER_SOL / 2;
+pub const BET: u64 = LAMPORTS_PER_SOL;
+pub const COMMISSION_BPS: u64 = 100;
+pub const WINDOW: i64 = 3_600;
+
+// Anchor's entry wants the account slice to live as long as its infos
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    prediction_market::entry(program_id, accounts, data)
+}
+
+pub struct Env {
+    pub context: ProgramTestContext,
+    pub admin: Keypair,
+    pub treasury: Pubkey,
+    pub creator: Keypair,
+    pub members: [Keypair; 3],
+    pub alice: Keypair,
+    pub bob: Keypair,
+}
+
+pub struct MarketAccounts {
+    pub market: Pubkey,
+    pub fee_vault: Pubkey,
+    pub votes: Pubkey,
+    pub end_time: i64,
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[PlatformConfig::SEED_PREFIX], &prediction_market::ID).0
+}
+
+pub fn user_bet_pda(user: &Pubkey, market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[UserBet::SEED_PREFIX, user.as_ref(), market.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+// initialize_config needs the program's upgrade authority, which a natively
+// loaded program does not have, so the config account is written directly
+fn config_account(admin: Pubkey, treasury: Pubkey) -> SolanaAccount {
+    let weights = |value| ScoringWeights { time: value, money: value, participants: value };
+    let config = PlatformConfig {
+        admin,
+        pending_admin: None,
+        treasury,
+        base_commission_bps: COMMISSION_BPS as u16,
+        late_commission_bps: COMMISSION_BPS as u16,
+        early_bet_threshold: 50,
+        early_exit_penalty_bps: 0,
+        late_exit_penalty_bps: 0,
+        min_bet_amount: 1_000,
+        min_velocity: 100 * LAMPORTS_PER_SOL,
+        velocity_factor: 100,
+        min_token_bet: 1,
+        min_token_velocity: 1,
+        min_market_duration: 60,
+        max_market_duration: 30 * 86_400,
+        claim_window: 7 * 86_400,
+        resolution_bond: BOND,
+        dispute_window: WINDOW,
+        settlement_timeout: 2 * WINDOW,
+        commit_window: WINDOW,
+        reveal_window: WINDOW,
+        oracle_program: Pubkey::new_unique(),
+        min_scoring_weights: weights(0),
+        max_scoring_weights: weights(100),
+        create_market_fee: LAMPORTS_PER_SOL / 10,
+        bump: Pubkey::find_program_address(&[PlatformConfig::SEED_PREFIX], &prediction_market::ID).1,
+    };
+    
+    let mut data = Vec::new();
+    config.try_serialize(&mut data).unwrap();
+    data.resize(8 + PlatformConfig::INIT_SPACE, 0);
+    
+    SolanaAccount {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: prediction_market::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub async fn setup() -> Env {
+    let mut program_test = ProgramTest::new(
+        "prediction_market",
+        prediction_market::ID,
+        processor!(process_instruction),
+    );
+    
+    let (admin, treasury) = (Keypair::new(), Pubkey::new_unique());
+    program_test.add_account(config_pda(), config_account(admin.pubkey(), treasury));
+    
+    let (creator, alice, bob) = (Keypair::new(), Keypair::new(), Keypair::new());
+    let members = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let funded = [treasury, creator.pubkey(), alice.pubkey(), bob.pubkey()]
+        .into_iter()
+        .chain(members.iter().map(|member| member.pubkey()));
+    for key in funded {
+        program_test.add_account(
+            key,
+            SolanaAccount::new(10 * LAMPORTS_PER_SOL, 0, &system_program::ID),
+        );
+    }
+    
+    Env {
+        context: program_test.start_with_context().await,
+        admin,
+        treasury,
+        creator,
+        members,
+        alice,
+        bob,
+    }
+}
+
+impl Env {
+    pub async fn send(&mut self, instruction: Instruction, signer: &Keypair) -> std::result::Result<(), TransactionError> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer, signer],
+            blockhash,
+        );
+        
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|err| err.unwrap())
+    }
+    
+    pub async fn now(&mut self) -> i64 {
+        self.context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+    }
+    
+    pub async fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+    
+    pub async fn balance(&mut self, key: Pubkey) -> u64 {
+        self.context.banks_client.get_balance(key).await.unwrap()
+    }
+    
+    pub async fn market(&mut self, key: Pubkey) -> Market {
+        let account = self.context.banks_client.get_account(key).await.unwrap().unwrap();
+        Market::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+    
+    pub async fn create_market(&mut self, mode: ResolutionMode) -> MarketAccounts {
+        let creator = self.creator.pubkey();
+        let market_id = 1u64;
+        let (market, _) = Pubkey::find_program_address(
+            &[b"market", creator.as_ref(), market_id.to_le_bytes().as_ref()],
+            &prediction_market::ID,
+        );
+        let (fee_vault, _) = Pubkey::find_program_address(
+            &[FeeVault::SEED_PREFIX, market.as_ref()],
+            &prediction_market::ID,
+        );
+        let (votes, _) = Pubkey::find_program_address(
+            &[ResolutionVotes::SEED_PREFIX, market.as_ref()],
+            &prediction_market::ID,
+        );
+        let end_time = self.now().await + 2 * WINDOW;
+        let (committee, committee_threshold) = match mode {
+            ResolutionMode::Committee => (self.members.iter().map(|member| member.pubkey()).collect(), 2),
+            _ => (Vec::new(), 0),
+        };
+        
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::CreateMarket {
+                market,
+                fee_vault,
+                config: config_pda(),
+                creator,
+                platform: self.treasury,
+                collateral_mint: None,
+                vault: None,
+                creator_token_account: None,
+                token_program: None,
+                price_feed: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::CreateMarket {
+                market_id,
+                question: "Will it happen?".to_string(),
+                options: vec!["Yes".to_string(), "No".to_string()],
+                end_time,
+                resolution: ResolutionParams {
+                    mode,
+                    tie_policy: TiePolicy::Void,
+                    scoring_weights: ScoringWeights { time: 40, money: 40, participants: 20 },
+                    resolver: None,
+                    oracle: None,
+                    committee,
+                    committee_threshold,
+                },
+                pricing: PricingMode::Parimutuel,
+            }
+            .data(),
+        };
+        let creator = self.creator.insecure_clone();
+        self.send(instruction, &creator).await.unwrap();
+        
+        MarketAccounts { market, fee_vault, votes, end_time }
+    }
+    
+    pub async fn place_bet(&mut self, accounts: &MarketAccounts, user: &Keypair, option_index: u8) {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::PlaceBet {
+                market: accounts.market,
+                user_bet: user_bet_pda(&user.pubkey(), &accounts.market),
+                fee_vault: accounts.fee_vault,
+                config: config_pda(),
+                user: user.pubkey(),
+                collateral_mint: None,
+                vault: None,
+                user_token_account: None,
+                token_program: None,
+                system_program: system_program::ID,
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::PlaceBet { outcome_index: option_index, amount: BET }.data(),
+        };
+        self.send(instruction, user).await.unwrap();
+    }
+    
+    pub async fn withdraw(&mut self, accounts: &MarketAccounts, user: &Keypair, option_index: u8, amount: u64) {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::WithdrawBet {
+                market: accounts.market,
+                user_bet: user_bet_pda(&user.pubkey(), &accounts.market),
+                fee_vault: accounts.fee_vault,
+                config: config_pda(),
+                user: user.pubkey(),
+                collateral_mint: None,
+                vault: None,
+                user_token_account: None,
+                token_program: None,
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::WithdrawBet { option_index, amount }.data(),
+        };
+        self.send(instruction, user).await.unwrap();
+    }
+    
+    pub async fn refund(&mut self, accounts: &MarketAccounts, user: &Keypair) {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::RefundBet {
+                market: accounts.market,
+                user_bet: user_bet_pda(&user.pubkey(), &accounts.market),
+                fee_vault: accounts.fee_vault,
+                config: config_pda(),
+                treasury: self.treasury,
+                creator: self.creator.pubkey(),
+                user: user.pubkey(),
+                collateral_mint: None,
+                vault: None,
+                user_token_account: None,
+                treasury_token_account: None,
+                creator_token_account: None,
+                token_program: None,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::RefundBet {}.data(),
+        };
+        self.send(instruction, user).await.unwrap();
+    }
+}
+
+pub fn custom_error(error: PredictionMarketError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+}
+
@@ -0,0 +1,163 @@
+// programs/prediction_market/tests/optimistic_dispute.rs
+//
+// Optimistic resolution when the admin never settles a dispute: once the
+// settlement deadline passes anyone can void the market and both bonds
+// go back to their owners.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::*;
+use solana_program_test::tokio;
+use solana_sdk::{signature::{Keypair, Signer}, transaction::TransactionError};
+
+fn proposal_pda(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ResolutionProposal::SEED_PREFIX, market.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+impl Env {
+    async fn propose(&mut self, accounts: &MarketAccounts, proposer: &Keypair, option_index: u8) {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::ProposeOutcome {
+                market: accounts.market,
+                proposal: proposal_pda(&accounts.market),
+                config: config_pda(),
+                proposer: proposer.pubkey(),
+                system_program: system_program::ID,
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::ProposeOutcome { option_index }.data(),
+        };
+        self.send(instruction, proposer).await.unwrap();
+    }
+    
+    async fn dispute(&mut self, accounts: &MarketAccounts, disputer: &Keypair) {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::DisputeOutcome {
+                market: accounts.market,
+                proposal: proposal_pda(&accounts.market),
+                config: config_pda(),
+                disputer: disputer.pubkey(),
+                system_program: system_program::ID,
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::DisputeOutcome {}.data(),
+        };
+        self.send(instruction, disputer).await.unwrap();
+    }
+    
+    async fn settle(
+        &mut self,
+        accounts: &MarketAccounts,
+        bond_recipient: Pubkey,
+        winning_option: u8,
+    ) -> std::result::Result<(), TransactionError> {
+        let admin = self.admin.insecure_clone();
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::SettleDispute {
+                market: accounts.market,
+                proposal: proposal_pda(&accounts.market),
+                bond_recipient,
+                fee_vault: accounts.fee_vault,
+                config: config_pda(),
+                treasury: self.treasury,
+                creator: self.creator.pubkey(),
+                admin: admin.pubkey(),
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::SettleDispute { winning_option }.data(),
+        };
+        self.send(instruction, &admin).await
+    }
+    
+    async fn expire(
+        &mut self,
+        accounts: &MarketAccounts,
+        proposer: Pubkey,
+        disputer: Pubkey,
+    ) -> std::result::Result<(), TransactionError> {
+        let instruction = Instruction {
+            program_id: prediction_market::ID,
+            accounts: prediction_market::accounts::ExpireDispute {
+                market: accounts.market,
+                proposal: proposal_pda(&accounts.market),
+                proposer,
+                disputer,
+                fee_vault: accounts.fee_vault,
+                config: config_pda(),
+                treasury: self.treasury,
+                creator: self.creator.pubkey(),
+                clock: anchor_lang::solana_program::sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: prediction_market::instruction::ExpireDispute {}.data(),
+        };
+        // Permissionless: only the fee payer signs
+        let payer = self.context.payer.insecure_clone();
+        self.send(instruction, &payer).await
+    }
+}
+
+#[tokio::test]
+async fn unsettled_dispute_voids_the_market_and_returns_both_bonds() {
+    let mut env = setup().await;
+    let accounts = env.create_market(ResolutionMode::Optimistic).await;
+    let (alice, bob) = (env.alice.insecure_clone(), env.bob.insecure_clone());
+    
+    env.place_bet(&accounts, &alice, 0).await;
+    env.place_bet(&accounts, &bob, 1).await;
+    
+    // Alice proposes her own option, Bob disputes it
+    env.warp_to(accounts.end_time).await;
+    let (alice_before, bob_before) = (env.balance(alice.pubkey()).await, env.balance(bob.pubkey()).await);
+    env.propose(&accounts, &alice, 0).await;
+    env.dispute(&accounts, &bob).await;
+    
+    let deadline = env.now().await + 2 * WINDOW;
+    
+    // Nothing to expire while the admin can still settle
+    env.warp_to(deadline - 1).await;
+    assert_eq!(
+        env.expire(&accounts, alice.pubkey(), bob.pubkey()).await.unwrap_err(),
+        custom_error(PredictionMarketError::SettlementPending)
+    );
+    
+    // The admin never acts; past the deadline settling is closed and
+    // anyone can void the market instead
+    env.warp_to(deadline).await;
+    assert_eq!(
+        env.settle(&accounts, alice.pubkey(), 0).await.unwrap_err(),
+        custom_error(PredictionMarketError::SettlementDeadlinePassed)
+    );
+    assert_eq!(
+        env.expire(&accounts, alice.pubkey(), alice.pubkey()).await.unwrap_err(),
+        custom_error(PredictionMarketError::InvalidBondRecipient)
+    );
+    env.expire(&accounts, alice.pubkey(), bob.pubkey()).await.unwrap();
+    
+    assert!(env.market(accounts.market).await.phase == MarketPhase::Cancelled);
+    assert!(env.context.banks_client.get_account(proposal_pda(&accounts.market)).await.unwrap().is_none());
+    
+    // Both bonds come back, the proposal rent to the proposer who paid it
+    assert_eq!(env.balance(alice.pubkey()).await, alice_before);
+    assert_eq!(env.balance(bob.pubkey()).await, bob_before);
+    
+    // Stakes are refundable as usual
+    env.refund(&accounts, &alice).await;
+    env.refund(&accounts, &bob).await;
+    assert!(env.context.banks_client.get_account(accounts.market).await.unwrap().is_none());
+}
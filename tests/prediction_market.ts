@@ -3,6 +3,21 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PredictionMarket } from "../target/types/prediction_market.js";
 import { assert, expect } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  createAccount,
+  createAssociatedTokenAccount,
+  createInitializeMintInstruction,
+  createInitializeNonTransferableMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  ExtensionType,
+  getAccount,
+  getMintLen,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 
 describe("prediction-market", () => {
   const provider = anchor.AnchorProvider.env();
@@ -10,7 +25,55 @@ describe("prediction-market", () => {
 
   const program = anchor.workspace.PredictionMarket as Program<PredictionMarket>;
   
+  const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [programDataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+
+  const configParams = {
+    treasury: provider.wallet.publicKey,
+    baseCommissionBps: 25, // 0.25%
+    lateCommissionBps: 50, // 0.50%
+    earlyBetThreshold: new anchor.BN(33),
+    earlyExitPenaltyBps: 100, // 1%
+    lateExitPenaltyBps: 500, // 5%
+    minBetAmount: new anchor.BN(5_000_000), // 0.005 SOL
+    minVelocity: new anchor.BN(100_000_000), // 0.1 SOL
+    velocityFactor: new anchor.BN(50),
+    minTokenBet: new anchor.BN(1_000_000), // 1 token
+    minTokenVelocity: new anchor.BN(100_000_000), // 100 tokens
+    minMarketDuration: new anchor.BN(60 * 60), // 1 hour
+    maxMarketDuration: new anchor.BN(365 * 24 * 60 * 60), // 1 year
+    claimWindow: new anchor.BN(30 * 24 * 60 * 60), // 30 days
+    resolutionBond: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
+    disputeWindow: new anchor.BN(24 * 60 * 60), // 1 day
+    settlementTimeout: new anchor.BN(7 * 24 * 60 * 60), // 1 week
+    commitWindow: new anchor.BN(24 * 60 * 60), // 1 day
+    revealWindow: new anchor.BN(24 * 60 * 60), // 1 day
+    oracleProgram: new anchor.web3.PublicKey("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH"), // Pyth
+    minScoringWeights: { time: 50, money: 0, participants: 0 },
+    maxScoringWeights: { time: 100, money: 50, participants: 20 },
+    createMarketFee: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL),
+  };
+
+  const algorithmic = {
+    mode: { algorithmic: {} },
+    tiePolicy: { splitPot: {} },
+    scoringWeights: { time: 70, money: 30, participants: 0 },
+    resolver: null,
+    oracle: null,
+    committee: [],
+    committeeThreshold: 0,
+  };
+
+  const parimutuel = { parimutuel: {} };
+
   let marketPda: anchor.web3.PublicKey;
+  let feeVaultPda: anchor.web3.PublicKey;
   let userBetPda: anchor.web3.PublicKey;
   const marketId = new anchor.BN(Date.now());
   
@@ -28,6 +91,85 @@ describe("prediction-market", () => {
     }
   });
 
+  it("Initializes the platform config", async () => {
+    await program.methods
+      .initializeConfig(provider.wallet.publicKey, configParams)
+      .accounts({
+        config: configPda,
+        authority: provider.wallet.publicKey,
+        program: program.programId,
+        programData: programDataPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await program.account.platformConfig.fetch(configPda);
+    assert.equal(config.admin.toBase58(), provider.wallet.publicKey.toBase58());
+    assert.equal(config.baseCommissionBps, 25);
+    assert.equal(config.minBetAmount.toNumber(), 5_000_000);
+  });
+
+  it("Transfers admin in two steps", async () => {
+    await program.methods
+      .proposeAdmin(user3.publicKey)
+      .accounts({ config: configPda, admin: provider.wallet.publicKey })
+      .rpc();
+
+    // Only the proposed key can accept
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accounts({ config: configPda, pendingAdmin: user2.publicKey })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+
+    await program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, pendingAdmin: user3.publicKey })
+      .signers([user3])
+      .rpc();
+
+    let config = await program.account.platformConfig.fetch(configPda);
+    assert.equal(config.admin.toBase58(), user3.publicKey.toBase58());
+    assert.isNull(config.pendingAdmin);
+
+    // Hand the role back for the remaining tests
+    await program.methods
+      .proposeAdmin(provider.wallet.publicKey)
+      .accounts({ config: configPda, admin: user3.publicKey })
+      .signers([user3])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, pendingAdmin: provider.wallet.publicKey })
+      .rpc();
+
+    config = await program.account.platformConfig.fetch(configPda);
+    assert.equal(config.admin.toBase58(), provider.wallet.publicKey.toBase58());
+  });
+
+  it("Only the admin can update the config", async () => {
+    try {
+      await program.methods
+        .updateConfig({ ...configParams, baseCommissionBps: 0 })
+        .accounts({
+          config: configPda,
+          admin: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
   it("Creates a market", async () => {
     const question = "Who will win the match?";
     const options = ["Team A", "Team B"];
@@ -41,11 +183,17 @@ describe("prediction-market", () => {
       ],
       program.programId
     );
+    [feeVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), marketPda.toBuffer()],
+      program.programId
+    );
 
     await program.methods
-      .createMarket(marketId, question, options, endTime)
+      .createMarket(marketId, question, options, endTime, algorithmic, parimutuel)
       .accounts({
         market: marketPda,
+        feeVault: feeVaultPda,
+        config: configPda,
         creator: provider.wallet.publicKey,
         platform: provider.wallet.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -57,6 +205,84 @@ describe("prediction-market", () => {
     assert.deepEqual(market.options, options);
     assert.equal(market.totalPool.toNumber(), 0);
     assert.equal(market.phase.betting !== undefined, true);
+    assert.equal(market.resolutionMode.algorithmic !== undefined, true);
+    assert.deepEqual(market.leadershipSeconds.map((s) => s.toNumber()), [0, 0]);
+    assert.equal(market.tiePolicy.splitPot !== undefined, true);
+    assert.equal(market.scoringWeights.time, 70);
+  });
+
+  it("Rejects a creation fee sent to a spoofed platform account", async () => {
+    const spoofedId = new anchor.BN(Date.now() + 1);
+    const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+    const [spoofedMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("market"),
+        user2.publicKey.toBuffer(),
+        spoofedId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [spoofedVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), spoofedMarket.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .createMarket(spoofedId, "Spoofed?", ["Yes", "No"], endTime, algorithmic, parimutuel)
+        .accounts({
+          market: spoofedMarket,
+          feeVault: spoofedVault,
+          config: configPda,
+          creator: user2.publicKey,
+          platform: user2.publicKey, // creator pays the fee to themselves
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidTreasury");
+    }
+  });
+
+  it("Rejects scoring weights outside the platform bounds", async () => {
+    const weightsId = new anchor.BN(Date.now() + 5);
+    const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+    const [weightsMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("market"),
+        provider.wallet.publicKey.toBuffer(),
+        weightsId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [weightsVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), weightsMarket.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .createMarket(weightsId, "Crowd pick?", ["Yes", "No"], endTime, {
+          ...algorithmic,
+          scoringWeights: { time: 50, money: 20, participants: 30 },
+        }, parimutuel)
+        .accounts({
+          market: weightsMarket,
+          feeVault: weightsVault,
+          config: configPda,
+          creator: provider.wallet.publicKey,
+          platform: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidScoringWeights");
+    }
   });
 
   it("Places a bet with early commission", async () => {
@@ -73,12 +299,15 @@ describe("prediction-market", () => {
     const optionIndex = 0;
 
     const marketBefore = await program.account.market.fetch(marketPda);
+    const vaultBalanceBefore = await provider.connection.getBalance(feeVaultPda);
     
     await program.methods
       .placeBet(optionIndex, betAmount)
       .accounts({
         market: marketPda,
         userBet: userBetPda,
+        feeVault: feeVaultPda,
+        config: configPda,
         user: provider.wallet.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
@@ -92,8 +321,9 @@ describe("prediction-market", () => {
     const expectedCommission = betAmount.toNumber() * 25 / 10000;
     const expectedNetAmount = betAmount.toNumber() - expectedCommission;
     
-    assert.equal(userBet.optionIndex, optionIndex);
+    assert.equal(userBet.positions[optionIndex].toNumber(), expectedNetAmount);
     assert.equal(userBet.amount.toNumber(), betAmount.toNumber());
+    assert.equal(userBet.netAmount.toNumber(), expectedNetAmount);
     assert.approximately(
       market.totalPool.toNumber(), 
       expectedNetAmount,
@@ -101,6 +331,11 @@ describe("prediction-market", () => {
     );
     assert.equal(market.optionPools[0].toNumber(), market.totalPool.toNumber());
     assert.equal(market.leadingOption, 0);
+
+    // Commission is actually collected in the fee vault
+    const vaultBalanceAfter = await provider.connection.getBalance(feeVaultPda);
+    assert.equal(vaultBalanceAfter - vaultBalanceBefore, expectedCommission);
+    assert.equal(market.totalFees.toNumber(), expectedCommission);
   });
 
   it("Another user places a larger bet on different option", async () => {
@@ -121,6 +356,8 @@ describe("prediction-market", () => {
       .accounts({
         market: marketPda,
         userBet: user2BetPda,
+        feeVault: feeVaultPda,
+        config: configPda,
         user: user2.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
@@ -140,6 +377,81 @@ describe("prediction-market", () => {
     assert.isAbove(odds1, odds0); // Team B should have higher odds
   });
 
+  it("Same user can hedge on another option", async () => {
+    const betAmount = new anchor.BN(0.05 * anchor.web3.LAMPORTS_PER_SOL);
+    const before = await program.account.userBet.fetch(userBetPda);
+
+    await program.methods
+      .placeBet(1, betAmount)
+      .accounts({
+        market: marketPda,
+        userBet: userBetPda,
+        feeVault: feeVaultPda,
+        config: configPda,
+        user: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+
+    const userBet = await program.account.userBet.fetch(userBetPda);
+    assert.equal(userBet.positions[0].toNumber(), before.positions[0].toNumber());
+    assert.isAbove(userBet.positions[1].toNumber(), 0);
+    assert.equal(
+      userBet.netAmount.toNumber(),
+      userBet.positions[0].toNumber() + userBet.positions[1].toNumber()
+    );
+
+    // Distinct bettors per option: the hedger now counts on both sides
+    const market = await program.account.market.fetch(marketPda);
+    assert.deepEqual(market.optionBettors, [1, 2]);
+  });
+
+  it("Cashes out part of a position with an exit penalty", async () => {
+    const [user2BetPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_bet"),
+        user2.publicKey.toBuffer(),
+        marketPda.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const betBefore = await program.account.userBet.fetch(user2BetPda);
+    const marketBefore = await program.account.market.fetch(marketPda);
+    const vaultBefore = await provider.connection.getBalance(feeVaultPda);
+    const withdrawAmount = betBefore.positions[1].divn(2);
+
+    await program.methods
+      .withdrawBet(1, withdrawAmount)
+      .accounts({
+        market: marketPda,
+        userBet: user2BetPda,
+        feeVault: feeVaultPda,
+        config: configPda,
+        user: user2.publicKey,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([user2])
+      .rpc();
+
+    const betAfter = await program.account.userBet.fetch(user2BetPda);
+    const marketAfter = await program.account.market.fetch(marketPda);
+    const vaultAfter = await provider.connection.getBalance(feeVaultPda);
+
+    // Early exit penalty is 1% and goes to the fee vault
+    const expectedPenalty = Math.floor((withdrawAmount.toNumber() * 100) / 10000);
+    assert.equal(
+      betAfter.positions[1].toNumber(),
+      betBefore.positions[1].sub(withdrawAmount).toNumber()
+    );
+    assert.equal(
+      marketAfter.optionPools[1].toNumber(),
+      marketBefore.optionPools[1].sub(withdrawAmount).toNumber()
+    );
+    assert.equal(vaultAfter - vaultBefore, expectedPenalty);
+  });
+
   it("Tests velocity limit", async () => {
     const hugeBetAmount = new anchor.BN(10 * anchor.web3.LAMPORTS_PER_SOL);
     
@@ -149,6 +461,8 @@ describe("prediction-market", () => {
         .accounts({
           market: marketPda,
           userBet: userBetPda,
+          feeVault: feeVaultPda,
+          config: configPda,
           user: provider.wallet.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
@@ -164,9 +478,13 @@ describe("prediction-market", () => {
   it("Cannot resolve market before end time", async () => {
     try {
       await program.methods
-        .resolveMarket()
+        .resolveMarket(null)
         .accounts({
           market: marketPda,
+          resolver: provider.wallet.publicKey,
+          feeVault: feeVaultPda,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
           creator: provider.wallet.publicKey,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
@@ -178,9 +496,1010 @@ describe("prediction-market", () => {
     }
   });
 
-  it("Only creator can resolve market", async () => {
-    // Bu test için market'in bitmesini simüle edemeyiz localnet'te
-    // Frontend testlerinde veya mainnet'te test edilebilir
+  it("Only the resolver or admin can resolve market", async () => {
+    const market = await program.account.market.fetch(marketPda);
+    assert.equal(market.resolver.toBase58(), provider.wallet.publicKey.toBase58());
+
+    try {
+      await program.methods
+        .resolveMarket(null)
+        .accounts({
+          market: marketPda,
+          resolver: user2.publicKey,
+          feeVault: feeVaultPda,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
+          creator: provider.wallet.publicKey,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("Optimistic markets only take proposals after they end", async () => {
+    const optimisticId = new anchor.BN(Date.now() + 2);
+    const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+    const [optimisticMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("market"),
+        provider.wallet.publicKey.toBuffer(),
+        optimisticId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [optimisticVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), optimisticMarket.toBuffer()],
+      program.programId
+    );
+    const [proposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), optimisticMarket.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createMarket(optimisticId, "Will it rain?", ["Yes", "No"], endTime, {
+        mode: { optimistic: {} },
+        tiePolicy: { void: {} },
+        scoringWeights: { time: 70, money: 30, participants: 0 },
+        resolver: null,
+        oracle: null,
+        committee: [],
+        committeeThreshold: 0,
+      }, parimutuel)
+      .accounts({
+        market: optimisticMarket,
+        feeVault: optimisticVault,
+        config: configPda,
+        creator: provider.wallet.publicKey,
+        platform: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .proposeOutcome(0)
+        .accounts({
+          market: optimisticMarket,
+          proposal: proposalPda,
+          config: configPda,
+          proposer: user2.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "MarketNotEnded");
+    }
+
+    // Algorithmic markets cannot be resolved optimistically
+    const [otherProposal] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), marketPda.toBuffer()],
+      program.programId
+    );
+    try {
+      await program.methods
+        .proposeOutcome(0)
+        .accounts({
+          market: marketPda,
+          proposal: otherProposal,
+          config: configPda,
+          proposer: user2.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "WrongResolutionMode");
+    }
+  });
+
+  it("Committee markets need a valid quorum and commit votes after they end", async () => {
+    const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+    const committeeMarket = (id: anchor.BN) => {
+      const [market] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          provider.wallet.publicKey.toBuffer(),
+          id.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const [feeVault] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), market.toBuffer()],
+        program.programId
+      );
+      return { market, feeVault };
+    };
+    const committee = [provider.wallet.publicKey, user2.publicKey, user3.publicKey];
+
+    // Threshold above the committee size
+    const badId = new anchor.BN(Date.now() + 3);
+    const bad = committeeMarket(badId);
+    try {
+      await program.methods
+        .createMarket(badId, "Who won?", ["A", "B"], endTime, {
+          mode: { committee: {} },
+          tiePolicy: { void: {} },
+          scoringWeights: { time: 70, money: 30, participants: 0 },
+          resolver: null,
+          oracle: null,
+          committee,
+          committeeThreshold: 4,
+        }, parimutuel)
+        .accounts({
+          market: bad.market,
+          feeVault: bad.feeVault,
+          config: configPda,
+          creator: provider.wallet.publicKey,
+          platform: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidCommittee");
+    }
+
+    const goodId = new anchor.BN(Date.now() + 4);
+    const good = committeeMarket(goodId);
+    await program.methods
+      .createMarket(goodId, "Who won?", ["A", "B"], endTime, {
+        mode: { committee: {} },
+        tiePolicy: { void: {} },
+        scoringWeights: { time: 70, money: 30, participants: 0 },
+        resolver: null,
+        oracle: null,
+        committee,
+        committeeThreshold: 2,
+      }, parimutuel)
+      .accounts({
+        market: good.market,
+        feeVault: good.feeVault,
+        config: configPda,
+        creator: provider.wallet.publicKey,
+        platform: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(good.market);
+    assert.equal(market.committee.length, 3);
+    assert.equal(market.committeeThreshold, 2);
+    assert.equal(
+      market.revealDeadline.sub(market.commitDeadline).toNumber(),
+      configParams.revealWindow.toNumber()
+    );
+
+    const [votesPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("votes"), good.market.toBuffer()],
+      program.programId
+    );
+    // hash(option || salt)
+    const salt = randomBytes(32);
+    const commitment = createHash("sha256")
+      .update(Buffer.concat([Buffer.from([0]), salt]))
+      .digest();
+    try {
+      await program.methods
+        .commitVote([...commitment])
+        .accounts({
+          market: good.market,
+          votes: votesPda,
+          config: configPda,
+          member: user2.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "MarketNotEnded");
+    }
+  });
+
+  it("Takes bets in a locally minted SPL token", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    // 9 decimals, so the 1 token minimum is 1_000_000_000 base units
+    const mint = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      9
+    );
+    const userTokenAccount = await createAccount(
+      provider.connection,
+      payer,
+      mint,
+      user2.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      userTokenAccount,
+      provider.wallet.publicKey,
+      50_000_000_000
+    );
+
+    const tokenId = new anchor.BN(Date.now() + 5);
+    const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+    const [tokenMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("market"),
+        provider.wallet.publicKey.toBuffer(),
+        tokenId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [tokenFeeVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), tokenMarket.toBuffer()],
+      program.programId
+    );
+    const [vault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), tokenMarket.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createMarket(tokenId, "Paid in tokens?", ["Yes", "No"], endTime, algorithmic, parimutuel)
+      .accounts({
+        market: tokenMarket,
+        feeVault: tokenFeeVault,
+        config: configPda,
+        creator: provider.wallet.publicKey,
+        platform: provider.wallet.publicKey,
+        collateralMint: mint,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    let market = await program.account.market.fetch(tokenMarket);
+    assert.equal(market.collateral.mint.toBase58(), mint.toBase58());
+    assert.equal(market.collateral.decimals, 9);
+    assert.equal(market.collateral.minBet.toNumber(), 1_000_000_000);
+
+    const [tokenUserBet] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_bet"), user2.publicKey.toBuffer(), tokenMarket.toBuffer()],
+      program.programId
+    );
+    const placeTokenBet = (amount: number) =>
+      program.methods
+        .placeBet(0, new anchor.BN(amount))
+        .accounts({
+          market: tokenMarket,
+          userBet: tokenUserBet,
+          feeVault: tokenFeeVault,
+          config: configPda,
+          user: user2.publicKey,
+          collateralMint: mint,
+          vault,
+          userTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([user2])
+        .rpc();
+
+    // Below the minimum once scaled to 9 decimals
+    try {
+      await placeTokenBet(999_999_999);
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "BetTooSmall");
+    }
+
+    const betAmount = 10_000_000_000;
+    await placeTokenBet(betAmount);
+
+    // Stake and commission both sit in the vault
+    const vaultAccount = await getAccount(provider.connection, vault);
+    assert.equal(Number(vaultAccount.amount), betAmount);
+
+    const commission = (betAmount * 25) / 10000;
+    market = await program.account.market.fetch(tokenMarket);
+    assert.equal(market.totalPool.toNumber(), betAmount - commission);
+    assert.equal(market.totalFees.toNumber(), commission);
+  });
+
+  describe("Token-2022 collateral", () => {
+    const payer = () => (provider.wallet as anchor.Wallet).payer;
+
+    // Token-2022 mint with 6 decimals and one extension
+    const createMint2022 = async (
+      extension: ExtensionType,
+      initExtension: (mint: anchor.web3.PublicKey) => anchor.web3.TransactionInstruction
+    ) => {
+      const mint = anchor.web3.Keypair.generate();
+      const space = getMintLen([extension]);
+      const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+      const tx = new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: provider.wallet.publicKey,
+          newAccountPubkey: mint.publicKey,
+          space,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        initExtension(mint.publicKey),
+        createInitializeMintInstruction(
+          mint.publicKey,
+          6,
+          provider.wallet.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      await provider.sendAndConfirm(tx, [mint]);
+      return mint.publicKey;
+    };
+
+    const tokenMarketAccounts = (id: anchor.BN, mint: anchor.web3.PublicKey) => {
+      const [market] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          provider.wallet.publicKey.toBuffer(),
+          id.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const [feeVault] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), market.toBuffer()],
+        program.programId
+      );
+      const [vault] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), market.toBuffer()],
+        program.programId
+      );
+      return {
+        market,
+        feeVault,
+        config: configPda,
+        creator: provider.wallet.publicKey,
+        platform: provider.wallet.publicKey,
+        collateralMint: mint,
+        vault,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      };
+    };
+
+    it("Credits only what a transfer-fee mint delivers", async () => {
+      // 1% transfer fee
+      const mint = await createMint2022(ExtensionType.TransferFeeConfig, (mint) =>
+        createInitializeTransferFeeConfigInstruction(
+          mint,
+          provider.wallet.publicKey,
+          provider.wallet.publicKey,
+          100,
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      const userTokenAccount = await createAccount(
+        provider.connection,
+        payer(),
+        mint,
+        user2.publicKey,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        provider.connection,
+        payer(),
+        mint,
+        userTokenAccount,
+        provider.wallet.publicKey,
+        50_000_000,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const id = new anchor.BN(Date.now() + 6);
+      const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+      const accounts = tokenMarketAccounts(id, mint);
+      await program.methods
+        .createMarket(id, "Fee on transfer?", ["Yes", "No"], endTime, algorithmic, parimutuel)
+        .accounts(accounts)
+        .rpc();
+
+      const [userBet] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user_bet"), user2.publicKey.toBuffer(), accounts.market.toBuffer()],
+        program.programId
+      );
+      const betAmount = 10_000_000;
+      await program.methods
+        .placeBet(0, new anchor.BN(betAmount))
+        .accounts({
+          market: accounts.market,
+          userBet,
+          feeVault: accounts.feeVault,
+          config: configPda,
+          user: user2.publicKey,
+          collateralMint: mint,
+          vault: accounts.vault,
+          userTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([user2])
+        .rpc();
+
+      const received = betAmount - betAmount / 100;
+      const vault = await getAccount(
+        provider.connection,
+        accounts.vault,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      assert.equal(Number(vault.amount), received);
+
+      const commission = Math.floor((received * 25) / 10000);
+      const market = await program.account.market.fetch(accounts.market);
+      const bet = await program.account.userBet.fetch(userBet);
+      assert.equal(market.optionPools[0].toNumber(), received - commission);
+      assert.equal(market.totalFees.toNumber(), commission);
+      assert.equal(bet.amount.toNumber(), received);
+    });
+
+    it("Rejects a non-transferable collateral mint", async () => {
+      const mint = await createMint2022(ExtensionType.NonTransferable, (mint) =>
+        createInitializeNonTransferableMintInstruction(mint, TOKEN_2022_PROGRAM_ID)
+      );
+
+      const id = new anchor.BN(Date.now() + 7);
+      const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+      try {
+        await program.methods
+          .createMarket(id, "Locked tokens?", ["Yes", "No"], endTime, algorithmic, parimutuel)
+          .accounts(tokenMarketAccounts(id, mint))
+          .rpc();
+
+        assert.fail("Should have failed");
+      } catch (error) {
+        assert.include(error.toString(), "UnsupportedMintExtension");
+      }
+    });
+  });
+
+  it("Mints and redeems complete sets of outcome shares", async () => {
+    const shareMints = [0, 1].map(
+      (optionIndex) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("shares"), marketPda.toBuffer(), Buffer.from([optionIndex])],
+          program.programId
+        )[0]
+    );
+    for (const [optionIndex, shareMint] of shareMints.entries()) {
+      await program.methods
+        .createShareMint(optionIndex)
+        .accounts({
+          market: marketPda,
+          shareMint,
+          payer: provider.wallet.publicKey,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const shareAccounts = [];
+    for (const shareMint of shareMints) {
+      shareAccounts.push(
+        await createAssociatedTokenAccount(
+          provider.connection,
+          (provider.wallet as anchor.Wallet).payer,
+          shareMint,
+          user3.publicKey
+        )
+      );
+    }
+    // [mint_0, account_0, mint_1, account_1]
+    const legs = shareMints.flatMap((shareMint, i) => [
+      { pubkey: shareMint, isWritable: true, isSigner: false },
+      { pubkey: shareAccounts[i], isWritable: true, isSigner: false },
+    ]);
+
+    const amount = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 2);
+    const marketBefore = await program.account.market.fetch(marketPda);
+    await program.methods
+      .mintCompleteSet(amount)
+      .accounts({
+        market: marketPda,
+        user: user3.publicKey,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(legs)
+      .signers([user3])
+      .rpc();
+
+    for (const shareAccount of shareAccounts) {
+      const account = await getAccount(provider.connection, shareAccount);
+      assert.equal(Number(account.amount), amount.toNumber());
+    }
+    let market = await program.account.market.fetch(marketPda);
+    assert.equal(market.shareCollateral.toNumber(), amount.toNumber());
+    market.shareSupply.forEach((supply) => assert.equal(supply.toNumber(), amount.toNumber()));
+    // Share collateral is kept out of the betting pool
+    assert.equal(market.totalPool.toNumber(), marketBefore.totalPool.toNumber());
+
+    // A single winning share is not redeemable before resolution
+    try {
+      await program.methods
+        .redeemShares(0, amount)
+        .accounts({
+          market: marketPda,
+          shareMint: shareMints[0],
+          userShareAccount: shareAccounts[0],
+          user: user3.publicKey,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user3])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "MarketNotResolved");
+    }
+
+    const half = amount.divn(2);
+    await program.methods
+      .redeemCompleteSet(half)
+      .accounts({
+        market: marketPda,
+        user: user3.publicKey,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(legs)
+      .signers([user3])
+      .rpc();
+
+    market = await program.account.market.fetch(marketPda);
+    assert.equal(market.shareCollateral.toNumber(), amount.sub(half).toNumber());
+
+    // Redeem the rest so the market can still close after the refund tests
+    await program.methods
+      .redeemCompleteSet(amount.sub(half))
+      .accounts({
+        market: marketPda,
+        user: user3.publicKey,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(legs)
+      .signers([user3])
+      .rpc();
+
+    market = await program.account.market.fetch(marketPda);
+    assert.equal(market.shareCollateral.toNumber(), 0);
+  });
+
+  it("Quotes LMSR trades from the cost function", async () => {
+    const lmsrId = new anchor.BN(Date.now() + 8);
+    const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+    const [lmsrMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("market"),
+        provider.wallet.publicKey.toBuffer(),
+        lmsrId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [lmsrFeeVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), lmsrMarket.toBuffer()],
+      program.programId
+    );
+
+    const liquidity = anchor.web3.LAMPORTS_PER_SOL;
+
+    // Every trade prices each option three times, so LMSR markets stay small
+    try {
+      await program.methods
+        .createMarket(lmsrId, "Too many outcomes?", ["A", "B", "C", "D", "E", "F"], endTime, algorithmic, {
+          lmsr: { liquidity: new anchor.BN(liquidity) },
+        })
+        .accounts({
+          market: lmsrMarket,
+          feeVault: lmsrFeeVault,
+          config: configPda,
+          creator: provider.wallet.publicKey,
+          platform: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidOptionCount");
+    }
+
+    await program.methods
+      .createMarket(lmsrId, "Priced continuously?", ["Yes", "No"], endTime, algorithmic, {
+        lmsr: { liquidity: new anchor.BN(liquidity) },
+      })
+      .accounts({
+        market: lmsrMarket,
+        feeVault: lmsrFeeVault,
+        config: configPda,
+        creator: provider.wallet.publicKey,
+        platform: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Creator funds the worst-case loss b * ln(2)
+    let market = await program.account.market.fetch(lmsrMarket);
+    assert.equal(market.subsidy.toNumber(), Math.ceil(liquidity * Math.LN2) + 1);
+
+    const [lmsrUserBet] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_bet"), user3.publicKey.toBuffer(), lmsrMarket.toBuffer()],
+      program.programId
+    );
+    const tradeAccounts = {
+      market: lmsrMarket,
+      userBet: lmsrUserBet,
+      user: user3.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    };
+
+    // Parimutuel bets are not available
+    try {
+      await program.methods
+        .placeBet(0, new anchor.BN(10_000_000))
+        .accounts({ ...tradeAccounts, feeVault: lmsrFeeVault, config: configPda })
+        .signers([user3])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "WrongPricingMode");
+    }
+
+    // C(q) = b ln(e^(q0/b) + e^(q1/b))
+    const shares = liquidity / 2;
+    const cost = (q0: number, q1: number) =>
+      liquidity * Math.log(Math.exp(q0 / liquidity) + Math.exp(q1 / liquidity));
+    const expectedCost = cost(shares, 0) - cost(0, 0);
+
+    try {
+      await program.methods
+        .buyShares(0, new anchor.BN(shares), new anchor.BN(Math.floor(expectedCost) - 10))
+        .accounts(tradeAccounts)
+        .signers([user3])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "SlippageExceeded");
+    }
+
+    const balanceBefore = await provider.connection.getBalance(user3.publicKey);
+    await program.methods
+      .buyShares(0, new anchor.BN(shares), new anchor.BN(Math.ceil(expectedCost) + 10))
+      .accounts(tradeAccounts)
+      .signers([user3])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(user3.publicKey);
+
+    market = await program.account.market.fetch(lmsrMarket);
+    assert.equal(market.optionPools[0].toNumber(), shares);
+    // Cost plus the user_bet rent and tx fee
+    assert.isAtLeast(balanceBefore - balanceAfter, Math.floor(expectedCost));
+
+    // Selling back refunds the cost, rounded down
+    await program.methods
+      .sellShares(0, new anchor.BN(shares), new anchor.BN(Math.floor(expectedCost) - 10))
+      .accounts({
+        market: lmsrMarket,
+        userBet: lmsrUserBet,
+        user: user3.publicKey,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([user3])
+      .rpc();
+
+    market = await program.account.market.fetch(lmsrMarket);
+    assert.equal(market.totalPool.toNumber(), 0);
+  });
+
+  it("Trades binary markets against a constant-product pool", async () => {
+    const cpmmId = new anchor.BN(Date.now() + 9);
+    const endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
+    const [cpmmMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("market"),
+        provider.wallet.publicKey.toBuffer(),
+        cpmmId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [cpmmFeeVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), cpmmMarket.toBuffer()],
+      program.programId
+    );
+    const createAccounts = {
+      market: cpmmMarket,
+      feeVault: cpmmFeeVault,
+      config: configPda,
+      creator: provider.wallet.publicKey,
+      platform: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Binary markets only
+    try {
+      await program.methods
+        .createMarket(cpmmId, "Three ways?", ["A", "B", "C"], endTime, algorithmic, {
+          cpmm: { feeBps: 100 },
+        })
+        .accounts(createAccounts)
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidOptionCount");
+    }
+
+    await program.methods
+      .createMarket(cpmmId, "Priced by the pool?", ["Yes", "No"], endTime, algorithmic, {
+        cpmm: { feeBps: 100 },
+      })
+      .accounts(createAccounts)
+      .rpc();
+
+    const [lpMint] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("lp_mint"), cpmmMarket.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .createLpMint()
+      .accounts({
+        market: cpmmMarket,
+        lpMint,
+        payer: provider.wallet.publicKey,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const userLpAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      lpMint,
+      user3.publicKey
+    );
+    const [cpmmUserBet] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_bet"), user3.publicKey.toBuffer(), cpmmMarket.toBuffer()],
+      program.programId
+    );
+
+    // First deposit sets even odds and mints one LP token per unit
+    const liquidity = anchor.web3.LAMPORTS_PER_SOL;
+    await program.methods
+      .addLiquidity(new anchor.BN(liquidity), new anchor.BN(liquidity))
+      .accounts({
+        market: cpmmMarket,
+        userBet: cpmmUserBet,
+        lpMint,
+        userLpAccount,
+        user: user3.publicKey,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([user3])
+      .rpc();
+
+    const lpAccount = await getAccount(provider.connection, userLpAccount);
+    assert.equal(Number(lpAccount.amount), liquidity);
+    let market = await program.account.market.fetch(cpmmMarket);
+    assert.deepEqual(market.amm.reserves.map((r) => r.toNumber()), [liquidity, liquidity]);
+
+    // x = sets minted so that (R + x - shares) * (R + x) = R^2, plus the 1% fee
+    const shares = liquidity / 2;
+    const sets = (-(2 * liquidity - shares) + Math.sqrt((2 * liquidity - shares) ** 2 + 4 * shares * liquidity)) / 2;
+    const expectedCost = sets / 0.99;
+    const tradeAccounts = {
+      market: cpmmMarket,
+      userBet: cpmmUserBet,
+      user: user3.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    };
+
+    try {
+      await program.methods
+        .buyShares(0, new anchor.BN(shares), new anchor.BN(Math.floor(expectedCost) - 10))
+        .accounts(tradeAccounts)
+        .signers([user3])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "SlippageExceeded");
+    }
+
+    await program.methods
+      .buyShares(0, new anchor.BN(shares), new anchor.BN(Math.ceil(expectedCost) + 10))
+      .accounts(tradeAccounts)
+      .signers([user3])
+      .rpc();
+
+    market = await program.account.market.fetch(cpmmMarket);
+    assert.equal(market.optionPools[0].toNumber(), shares);
+    // Trader shares plus pool shares are backed one to one on both sides
+    const [yes, no] = market.amm.reserves.map((r) => r.toNumber());
+    assert.equal(shares + yes, no);
+    assert.isAbove(no, yes);
+
+    // LP tokens only pay out once the market settles
+    try {
+      await program.methods
+        .withdrawLiquidity(new anchor.BN(liquidity))
+        .accounts({
+          market: cpmmMarket,
+          lpMint,
+          userLpAccount,
+          user: user3.publicKey,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user3])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "MarketNotResolved");
+    }
+  });
+
+  it("Only the platform admin can withdraw fees", async () => {
+    try {
+      await program.methods
+        .withdrawFees()
+        .accounts({
+          market: marketPda,
+          feeVault: feeVaultPda,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
+          admin: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("Cannot close a market that is still open", async () => {
+    try {
+      await program.methods
+        .closeMarket()
+        .accounts({
+          market: marketPda,
+          feeVault: feeVaultPda,
+          config: configPda,
+          creator: provider.wallet.publicKey,
+          treasury: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "MarketNotClosable");
+    }
+  });
+
+  it("Only the creator or admin can cancel a market", async () => {
+    try {
+      await program.methods
+        .cancelMarket()
+        .accounts({
+          market: marketPda,
+          config: configPda,
+          authority: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("Admin cancels a market and bettors get full refunds", async () => {
+    const [user2BetPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_bet"),
+        user2.publicKey.toBuffer(),
+        marketPda.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .cancelMarket()
+      .accounts({
+        market: marketPda,
+        config: configPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    let market = await program.account.market.fetch(marketPda);
+    assert.equal(market.phase.cancelled !== undefined, true);
+
+    const userBet = await program.account.userBet.fetch(user2BetPda);
+    const balanceBefore = await provider.connection.getBalance(user2.publicKey);
+
+    await program.methods
+      .refundBet()
+      .accounts({
+        market: marketPda,
+        userBet: user2BetPda,
+        feeVault: feeVaultPda,
+        config: configPda,
+        treasury: provider.wallet.publicKey,
+        creator: provider.wallet.publicKey,
+        user: user2.publicKey,
+      })
+      .signers([user2])
+      .rpc();
+
+    // Stake + commission + user_bet rent, minus the tx fee
+    const balanceAfter = await provider.connection.getBalance(user2.publicKey);
+    assert.isAbove(balanceAfter - balanceBefore, userBet.amount.toNumber() - 10_000);
+    assert.isNull(await program.account.userBet.fetchNullable(user2BetPda));
+
+    // Only the creator's hedge is left on option 1
+    market = await program.account.market.fetch(marketPda);
+    const creatorBet = await program.account.userBet.fetch(userBetPda);
+    assert.equal(
+      market.optionPools[1].toNumber(),
+      creatorBet.positions[1].toNumber()
+    );
+  });
+
+  it("Last refund returns the market rent to the creator", async () => {
+    await program.methods
+      .refundBet()
+      .accounts({
+        market: marketPda,
+        userBet: userBetPda,
+        feeVault: feeVaultPda,
+        config: configPda,
+        treasury: provider.wallet.publicKey,
+        creator: provider.wallet.publicKey,
+        user: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    assert.isNull(await program.account.market.fetchNullable(marketPda));
+    assert.isNull(await program.account.feeVault.fetchNullable(feeVaultPda));
   });
 
   // Daha fazla test: emergency pause, claim winnings vs.